@@ -0,0 +1,13 @@
+//! Feeds arbitrary input to a day's parser, picked by the first byte. Run
+//! with `cargo fuzz run parse` from this directory.
+#![no_main]
+
+use aoc2024::solution::{solution, Params, DAYS};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Some((day, input)) = data.split_first() {
+        let parse = solution(*day as usize % DAYS + 1).unwrap();
+        let _ = parse(&String::from_utf8_lossy(input), &Params::new());
+    }
+});
@@ -0,0 +1,249 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind},
+    queue,
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{self, ClearType},
+};
+
+use crate::{
+    day06, day14, day15, day16,
+    error::Result,
+    geometry::Point,
+    solution::{Part, Solution},
+    utils::Grid,
+};
+
+/// One step of a simulation: the map drawn as characters, and a line
+/// describing the state of whatever is moving around it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub map: Grid<char>,
+    pub status: String,
+}
+
+pub type Frames = Box<dyn Iterator<Item = Frame>>;
+
+/// A day whose simulation can be watched step by step.
+pub trait Animate: Solution {
+    /// The steps of the simulation for `part`, for days where the parts
+    /// simulate different things.
+    fn frames(self, part: Part) -> Frames;
+}
+
+type Animator = fn(&str, Part) -> Result<Frames>;
+
+fn animator<A: Animate>(input: &str, part: Part) -> Result<Frames> {
+    Ok(A::parse(input)?.frames(part))
+}
+
+const ANIMATIONS: [(usize, Animator); 4] = [
+    (6, animator::<day06::Map>),
+    (14, animator::<day14::Bathroom>),
+    (15, animator::<day15::Warehouse>),
+    (16, animator::<day16::ReindeerMaze>),
+];
+
+/// Days that can be animated.
+pub fn days() -> impl Iterator<Item = usize> {
+    ANIMATIONS.iter().map(|(day, _)| *day)
+}
+
+/// Parses `input` for `day` and returns the frames of its simulation, or
+/// `None` if the day can't be animated.
+pub fn animation(day: usize, input: &str, part: Part) -> Option<Result<Frames>> {
+    let (_, animator) = ANIMATIONS.iter().find(|(d, _)| *d == day)?;
+    Some(animator(input, part))
+}
+
+fn colour(c: char) -> Color {
+    match c {
+        '#' => Color::DarkGrey,
+        'O' | '[' | ']' => Color::Yellow,
+        '@' | '^' | '>' | 'v' | '<' => Color::Red,
+        'X' | '+' => Color::Blue,
+        'S' | 'E' | '0'..='9' => Color::Green,
+        _ => Color::Reset,
+    }
+}
+
+/// Speed and starting state of playback.
+#[derive(Clone, Copy, Debug)]
+pub struct Playback {
+    pub fps: f64,
+    pub paused: bool,
+}
+
+/// Plays `frames` in the terminal until they run out and the user quits.
+/// Space pauses, `n` or right steps one frame while paused, `+` and `-`
+/// change speed and `q` quits. When standard output isn't a terminal every
+/// frame is printed in turn instead.
+pub fn play(frames: Frames, playback: Playback) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    if !stdout.is_terminal() {
+        for frame in frames {
+            write!(stdout, "{}{}\n\n", frame.map, frame.status)?;
+        }
+        return Ok(());
+    }
+
+    terminal::enable_raw_mode()?;
+    queue!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = play_in_terminal(&mut stdout, frames, playback);
+    queue!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    stdout.flush()?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn play_in_terminal(
+    out: &mut impl Write,
+    mut frames: Frames,
+    playback: Playback,
+) -> io::Result<()> {
+    let Playback {
+        mut fps,
+        mut paused,
+    } = playback;
+    let mut current = frames.next();
+    let mut finished = current.is_none();
+    let mut next_at = Instant::now();
+    loop {
+        if let Some(frame) = &current {
+            draw(out, frame, fps, paused, finished)?;
+        }
+
+        let mut step = false;
+        let wait = next_at.saturating_duration_since(Instant::now());
+        if event::poll(if paused || finished {
+            Duration::from_secs(60)
+        } else {
+            wait
+        })? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(' ') => paused = !paused,
+                    KeyCode::Char('n') | KeyCode::Right => step = paused,
+                    KeyCode::Char('+') | KeyCode::Char('=') => fps = (fps * 2.0).min(1000.0),
+                    KeyCode::Char('-') => fps = (fps / 2.0).max(0.25),
+                    _ => {}
+                }
+            }
+        }
+
+        let due = !paused && Instant::now() >= next_at;
+        if !finished && (step || due) {
+            match frames.next() {
+                Some(frame) => current = Some(frame),
+                None => finished = true,
+            }
+            next_at = Instant::now() + Duration::from_secs_f64(1.0 / fps);
+        }
+    }
+}
+
+fn draw(
+    out: &mut impl Write,
+    frame: &Frame,
+    fps: f64,
+    paused: bool,
+    finished: bool,
+) -> io::Result<()> {
+    queue!(out, cursor::MoveTo(0, 0))?;
+    for y in 0..frame.map.height() {
+        // Only change colour between runs of differently coloured cells
+        let mut current = None;
+        for x in 0..frame.map.width() {
+            let c = frame.map[Point::new(y as isize, x as isize)];
+            let colour = colour(c);
+            if current != Some(colour) {
+                queue!(out, SetForegroundColor(colour))?;
+                current = Some(colour);
+            }
+            queue!(out, Print(c))?;
+        }
+        queue!(out, ResetColor, Print("\r\n"))?;
+    }
+    let state = match (finished, paused) {
+        (true, _) => "finished",
+        (_, true) => "paused",
+        _ => "playing",
+    };
+    queue!(
+        out,
+        terminal::Clear(ClearType::UntilNewLine),
+        Print(&frame.status),
+        Print("\r\n"),
+        terminal::Clear(ClearType::UntilNewLine),
+        Print(format!(
+            "{} at {} fps · space pause · n step · +/- speed · q quit",
+            state, fps
+        )),
+    )?;
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::get_variant;
+
+    use super::*;
+
+    fn last(frames: Frames) -> (usize, Frame) {
+        frames
+            .enumerate()
+            .last()
+            .map(|(i, frame)| (i + 1, frame))
+            .unwrap()
+    }
+
+    #[test]
+    fn guard() {
+        let map = day06::Map::new(&get_variant(6, "small").unwrap()).unwrap();
+        let (count, frame) = last(map.frames(Part::One));
+        assert!(frame.status.ends_with("41 positions visited"));
+        assert_eq!(
+            41,
+            frame
+                .map
+                .iter()
+                .filter(|(_, c)| "X^>v<".contains(**c))
+                .count()
+        );
+        assert!(count > 41);
+    }
+
+    #[test]
+    fn warehouse() {
+        let input = get_variant(15, "small").unwrap();
+        let (count, frame) = last(day15::Warehouse::new(&input).unwrap().frames(Part::One));
+        assert_eq!(701, count);
+        assert!(frame.status.ends_with("GPS sum 10092"));
+        let (_, frame) = last(day15::Warehouse::new(&input).unwrap().frames(Part::Two));
+        assert!(frame.status.ends_with("GPS sum 9021"));
+        assert_eq!(20, frame.map.width());
+    }
+
+    #[test]
+    fn reindeer() {
+        let maze = day16::ReindeerMaze::new(&get_variant(16, "small").unwrap()).unwrap();
+        let (_, frame) = last(maze.frames(Part::One));
+        assert!(frame.status.starts_with("score 7036"));
+    }
+
+    #[test]
+    fn registry() {
+        assert!(animation(1, "", Part::One).is_none());
+        assert!(animation(6, "", Part::One).unwrap().is_err());
+        assert_eq!(vec![6, 14, 15, 16], days().collect::<Vec<_>>());
+    }
+}
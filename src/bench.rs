@@ -0,0 +1,261 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::{Error, Result},
+    solution::{solution, Params},
+};
+
+/// The timed stages of a day, in the order they run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    fn key(&self) -> &'static str {
+        match self {
+            Self::Parse => "parse",
+            Self::Part1 => "part1",
+            Self::Part2 => "part2",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|phase| phase.key() == key)
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Parse => "parse",
+            Self::Part1 => "part 1",
+            Self::Part2 => "part 2",
+        })
+    }
+}
+
+/// Median and spread (slowest minus fastest) of a set of samples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub median: Duration,
+    pub spread: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples to summarise");
+        samples.sort_unstable();
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        let spread = samples[samples.len() - 1] - samples[0];
+        Self { median, spread }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>10.1?} ± {:.1?}", self.median, self.spread)
+    }
+}
+
+/// Times parse, part 1 and part 2 of a day separately over `iterations` runs.
+pub fn bench(day: usize, input: &str, iterations: usize) -> Result<Vec<(Phase, Stats)>> {
+    let parse = solution(day).expect("every day is registered");
+    let mut samples = [Vec::new(), Vec::new(), Vec::new()];
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        let solver = parse(input, &Params::new())?;
+        samples[0].push(start.elapsed());
+        samples[1].push(time(|| solver.part1()));
+        samples[2].push(time(|| solver.part2()));
+    }
+    Ok(Phase::ALL
+        .into_iter()
+        .zip(samples.iter_mut())
+        .map(|(phase, samples)| (phase, Stats::new(samples)))
+        .collect())
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> Duration {
+    let start = Instant::now();
+    std::hint::black_box(f());
+    start.elapsed()
+}
+
+/// Median times from an earlier run, keyed by day and phase.
+///
+/// Stored as one `day phase nanoseconds` line per entry so it diffs cleanly.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Baseline(BTreeMap<(usize, Phase), Duration>);
+
+impl Baseline {
+    /// Loads a baseline, or an empty one if the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text).map_err(|reason| Error::Input {
+                source: path.display().to_string(),
+                reason,
+            }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::Input {
+                source: path.display().to_string(),
+                reason: e.to_string(),
+            }),
+        }
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut times = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let entry = match fields[..] {
+                [] => continue,
+                [day, phase, nanos] => day
+                    .parse()
+                    .ok()
+                    .zip(Phase::from_key(phase))
+                    .zip(nanos.parse().ok().map(Duration::from_nanos)),
+                _ => None,
+            };
+            let (key, time) = entry.ok_or_else(|| {
+                format!(
+                    "line {}: expected `day phase nanoseconds`, found `{}`",
+                    i + 1,
+                    line
+                )
+            })?;
+            times.insert(key, time);
+        }
+        Ok(Self(times))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, self.to_string()).map_err(|e| Error::Input {
+            source: path.display().to_string(),
+            reason: e.to_string(),
+        })
+    }
+
+    pub fn get(&self, day: usize, phase: Phase) -> Option<Duration> {
+        self.0.get(&(day, phase)).copied()
+    }
+
+    pub fn insert(&mut self, day: usize, phase: Phase, time: Duration) {
+        self.0.insert((day, phase), time);
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ((day, phase), time) in self.0.iter() {
+            writeln!(f, "{} {} {}", day, phase.key(), time.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+/// How a median compares to its baseline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Change {
+    /// Slower than the baseline by more than the threshold.
+    Regression(f64),
+    Within(f64),
+}
+
+impl Change {
+    /// Compares `current` against `baseline`, where `threshold` is the
+    /// fraction of slowdown tolerated before flagging a regression.
+    pub fn new(current: Duration, baseline: Duration, threshold: f64) -> Self {
+        let ratio = current.as_secs_f64() / baseline.as_secs_f64().max(f64::EPSILON) - 1.0;
+        if ratio > threshold {
+            Self::Regression(ratio)
+        } else {
+            Self::Within(ratio)
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Regression(ratio) => write!(f, "{:+.0}% REGRESSION", ratio * 100.0),
+            Self::Within(ratio) => write!(f, "{:+.0}%", ratio * 100.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::new(&mut [ms(5), ms(1), ms(3)]);
+        assert_eq!((ms(3), ms(4)), (stats.median, stats.spread));
+        let stats = Stats::new(&mut [ms(4), ms(1), ms(2), ms(9)]);
+        assert_eq!((ms(3), ms(8)), (stats.median, stats.spread));
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.insert(6, Phase::Part2, ms(250));
+        baseline.insert(22, Phase::Parse, Duration::from_nanos(1234));
+        let text = baseline.to_string();
+        assert_eq!("6 part2 250000000\n22 parse 1234\n", text);
+        assert_eq!(Ok(baseline), Baseline::parse(&text));
+        assert!(Baseline::parse("6 part3 1").is_err());
+    }
+
+    #[test]
+    fn regression() {
+        assert!(matches!(
+            Change::new(ms(120), ms(100), 0.1),
+            Change::Regression(_)
+        ));
+        assert!(matches!(
+            Change::new(ms(105), ms(100), 0.1),
+            Change::Within(_)
+        ));
+        assert!(matches!(
+            Change::new(ms(50), ms(100), 0.1),
+            Change::Within(_)
+        ));
+    }
+
+    #[test]
+    fn bench_small() {
+        let input = crate::input::get_variant(1, "small").unwrap();
+        let timings = bench(1, &input, 3).unwrap();
+        assert_eq!(
+            Phase::ALL.to_vec(),
+            timings.iter().map(|t| t.0).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,187 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::solution::{version_in, Answer};
+
+/// Environment variable that points the cache at a different directory.
+pub const CACHE_DIR_VAR: &str = "AOC_CACHE_DIR";
+
+/// What identifies a cached answer: the event, day and part, the version of the
+/// solver that computed it, and a hash of the input it was computed from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Key {
+    pub year: u16,
+    pub day: usize,
+    pub part: u8,
+    pub version: u32,
+    pub input: u64,
+}
+
+impl Key {
+    pub fn new(year: u16, day: usize, part: u8, input: &str) -> Self {
+        Self {
+            year,
+            day,
+            part,
+            version: version_in(year, day).expect("every day is registered"),
+            input: hash(input.as_bytes()),
+        }
+    }
+
+    fn file_name(&self) -> String {
+        format!(
+            "{}-day{:02}-part{}-v{}-{:016x}.json",
+            self.year, self.day, self.part, self.version, self.input
+        )
+    }
+}
+
+/// 64-bit FNV-1a, which unlike the standard library's hasher is the same
+/// from one build to the next.
+fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// A cached answer, with the artifacts that came with it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub answer: Answer,
+    pub artifacts: BTreeMap<String, String>,
+}
+
+/// An [`Entry`] as stored on disk, with the answer written the way it prints
+/// so the file reads naturally.
+#[derive(Serialize, Deserialize)]
+struct Stored {
+    #[serde(rename = "type")]
+    kind: String,
+    answer: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    artifacts: BTreeMap<String, String>,
+}
+
+impl From<&Entry> for Stored {
+    fn from(entry: &Entry) -> Self {
+        Self {
+            kind: entry.answer.kind().to_string(),
+            answer: entry.answer.to_string(),
+            artifacts: entry.artifacts.clone(),
+        }
+    }
+}
+
+impl Stored {
+    fn entry(self) -> Option<Entry> {
+        let answer = match self.kind.as_str() {
+            "int" => Answer::Int(self.answer.parse().ok()?),
+            "uint" => Answer::UInt(self.answer.parse().ok()?),
+            "text" => Answer::Text(self.answer),
+            "none" => Answer::None,
+            _ => return None,
+        };
+        Some(Entry {
+            answer,
+            artifacts: self.artifacts,
+        })
+    }
+}
+
+/// Answers from earlier runs, one file per [`Key`] in a directory.
+///
+/// The cache is best effort: entries that can't be read are treated as
+/// missing, and failing to write one doesn't stop a run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `.aoc_cache` unless overridden by `AOC_CACHE_DIR`.
+    pub fn default_dir() -> PathBuf {
+        env::var_os(CACHE_DIR_VAR)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(".aoc_cache"))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn get(&self, key: &Key) -> Option<Entry> {
+        let text = fs::read_to_string(self.dir.join(key.file_name())).ok()?;
+        serde_json::from_str::<Stored>(&text).ok()?.entry()
+    }
+
+    pub fn put(&self, key: &Key, entry: &Entry) {
+        let _ = self.try_put(key, entry);
+    }
+
+    /// Writes to a temporary file first, so days running in parallel never
+    /// see half an entry.
+    fn try_put(&self, key: &Key, entry: &Entry) -> std::io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(key.file_name());
+        let temp = path.with_extension(format!("{}.tmp", std::process::id()));
+        let text = serde_json::to_string(&Stored::from(entry)).map_err(std::io::Error::other)?;
+        fs::write(&temp, text)?;
+        fs::rename(&temp, &path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::YEAR;
+
+    use super::*;
+
+    fn temp_cache(name: &str) -> Cache {
+        let dir = env::temp_dir().join(format!("aoc2024-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Cache::new(dir)
+    }
+
+    #[test]
+    fn round_trip() {
+        let cache = temp_cache("round-trip");
+        let key = Key::new(YEAR, 14, 2, "p=0,4 v=3,-3\n");
+        assert_eq!(None, cache.get(&key));
+        let entries = [
+            Answer::Int(-3),
+            Answer::UInt(7492),
+            Answer::Text("co,de,ka,ta".to_string()),
+            Answer::None,
+        ]
+        .map(|answer| Entry {
+            answer,
+            artifacts: BTreeMap::from([("tree".to_string(), "#.\n.#\n".to_string())]),
+        });
+        for entry in &entries {
+            cache.put(&key, entry);
+            assert_eq!(Some(entry), cache.get(&key).as_ref());
+        }
+        fs::remove_dir_all(cache.dir()).unwrap();
+    }
+
+    #[test]
+    fn keys() {
+        let key = Key::new(YEAR, 6, 1, "....#\n");
+        assert_eq!(key, Key::new(YEAR, 6, 1, "....#\n"));
+        assert_ne!(key, Key::new(YEAR, 6, 1, "...#.\n"));
+        assert_ne!(key, Key::new(YEAR, 6, 2, "....#\n"));
+        assert_ne!(key.file_name(), Key { version: 2, ..key }.file_name());
+        assert_ne!(key.file_name(), Key { year: 2025, ..key }.file_name());
+        assert_eq!(0xcbf29ce484222325, hash(b""));
+        assert_eq!(0xaf63dc4c8601ec8c, hash(b"a"));
+    }
+}
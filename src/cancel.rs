@@ -0,0 +1,127 @@
+use std::{
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+/// A deadline for whatever is solving on the current thread. Long-running
+/// loops call [`checkpoint`] to give up once it has passed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token {
+    deadline: Instant,
+}
+
+/// The unwind payload of a [`checkpoint`] past its deadline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimedOut;
+
+thread_local! {
+    static CURRENT: Cell<Option<Token>> = const { Cell::new(None) };
+}
+
+/// Puts back the token that was current before, even when unwinding.
+struct Restore(Option<Token>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.0));
+    }
+}
+
+impl Token {
+    /// A token that expires `budget` from now.
+    pub fn after(budget: Duration) -> Self {
+        Self {
+            deadline: Instant::now() + budget,
+        }
+    }
+
+    pub fn expired(&self) -> bool {
+        Instant::now() >= self.deadline
+    }
+
+    /// Runs `f` with this token current on this thread. Checkpoints past the
+    /// deadline unwind out of `f`.
+    pub fn enter<T>(self, f: impl FnOnce() -> T) -> T {
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self))));
+        f()
+    }
+
+    /// Runs `f` with this token current, stopping it at the first checkpoint
+    /// past the deadline. Other panics carry on unwinding.
+    pub fn run<T>(self, f: impl FnOnce() -> T) -> Result<T, TimedOut> {
+        panic::catch_unwind(AssertUnwindSafe(|| self.enter(f))).map_err(|payload| {
+            match payload.downcast::<TimedOut>() {
+                Ok(timed_out) => *timed_out,
+                Err(payload) => panic::resume_unwind(payload),
+            }
+        })
+    }
+}
+
+/// The token current on this thread, to hand on to threads doing part of the
+/// same work with [`within`].
+pub fn current() -> Option<Token> {
+    CURRENT.with(Cell::get)
+}
+
+/// Runs `f` with `token` current, if there is one.
+pub fn within<T>(token: Option<Token>, f: impl FnOnce() -> T) -> T {
+    match token {
+        Some(token) => token.enter(f),
+        None => f(),
+    }
+}
+
+/// Unwinds with [`TimedOut`] if this thread's token has expired. Does
+/// nothing when there's no token.
+pub fn checkpoint() {
+    if current().is_some_and(|token| token.expired()) {
+        // Not a panic, so the panic hook doesn't report it
+        panic::resume_unwind(Box::new(TimedOut));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use super::*;
+
+    fn forever() -> u32 {
+        loop {
+            checkpoint();
+        }
+    }
+
+    #[test]
+    fn timeout() {
+        let token = Token::after(Duration::from_millis(20));
+        assert_eq!(Err(TimedOut), token.run(forever));
+        assert_eq!(None, current());
+        assert_eq!(Ok(3), Token::after(Duration::from_secs(60)).run(|| 3));
+        // Without a token checkpoints never stop anything
+        checkpoint();
+    }
+
+    #[test]
+    fn other_panics() {
+        let token = Token::after(Duration::from_secs(60));
+        let result = panic::catch_unwind(|| token.run(|| panic!("not a timeout")));
+        assert!(result.is_err());
+        assert_eq!(None, current());
+    }
+
+    #[test]
+    fn threads() {
+        let token = Token::after(Duration::from_millis(20));
+        let result = token.run(|| {
+            let token = current();
+            (0..4)
+                .into_par_iter()
+                .map(|_| within(token, forever))
+                .sum::<u32>()
+        });
+        assert_eq!(Err(TimedOut), result);
+    }
+}
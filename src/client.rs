@@ -0,0 +1,370 @@
+use std::{
+    cell::Cell,
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    error::{Error, Result},
+    input::file_name,
+    solution::YEAR,
+};
+
+/// Where puzzles come from unless `AOC_BASE_URL` says otherwise.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Environment variable that points the client at a different site, such as
+/// a local stand-in.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+/// Environment variable holding the `session` cookie to log in with.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// The least time between two requests, so the site isn't hammered.
+pub const INTERVAL: Duration = Duration::from_secs(5);
+
+/// The site's reply to an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// Wrong, perhaps with a hint such as "too high".
+    Incorrect(Option<String>),
+    /// An answer was given too recently; the message says how long is left.
+    Wait(String),
+    /// The part has been solved already, or isn't unlocked yet.
+    WrongLevel,
+    /// A reply that didn't look like any of the others, as text.
+    Unknown(String),
+}
+
+impl Verdict {
+    /// Reads the verdict from the page the site answers with.
+    fn parse(page: &str) -> Self {
+        let message = article(page);
+        if message.contains("That's the right answer") {
+            Self::Correct
+        } else if message.contains("That's not the right answer") {
+            let hint = ["too high", "too low"]
+                .into_iter()
+                .find(|hint| message.contains(hint))
+                .map(str::to_string);
+            Self::Incorrect(hint)
+        } else if message.contains("You gave an answer too recently") {
+            Self::Wait(message)
+        } else if message.contains("You don't seem to be solving the right level") {
+            Self::WrongLevel
+        } else {
+            Self::Unknown(message)
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Incorrect(Some(hint)) => write!(f, "incorrect, {}", hint),
+            Self::Incorrect(None) => write!(f, "incorrect"),
+            Self::Wait(message) | Self::Unknown(message) => write!(f, "{}", message),
+            Self::WrongLevel => write!(f, "already solved, or not unlocked yet"),
+        }
+    }
+}
+
+/// The text of a page's `<article>`, or the whole page, without tags.
+fn article(page: &str) -> String {
+    let body = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in body.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// What [`Client::fetch`] found.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// Downloaded to this path.
+    Downloaded(PathBuf),
+    /// Already at this path, so not downloaded again.
+    Cached(PathBuf),
+    /// The puzzle isn't unlocked yet.
+    Locked,
+}
+
+/// Fetches puzzle inputs and submits answers for one event, logged in with a
+/// session token. Requests from one client are spaced out by at least
+/// [`INTERVAL`], however quickly they're asked for.
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: u16,
+    interval: Duration,
+    last: Cell<Option<Instant>>,
+}
+
+impl Client {
+    /// A client for [`YEAR`] talking to the site at `base_url`.
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent("github.com/agneum14/aoc2024")
+            .timeout(Duration::from_secs(30))
+            .build();
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year: YEAR,
+            interval: INTERVAL,
+            last: Cell::new(None),
+        }
+    }
+
+    /// A client configured by `AOC_BASE_URL` and `AOC_SESSION`.
+    pub fn from_env() -> Result<Self> {
+        let session = env::var(SESSION_VAR).map_err(|_| Error::Input {
+            source: SESSION_VAR.to_string(),
+            reason: "not set; copy the `session` cookie from a logged-in browser".to_string(),
+        })?;
+        let base_url = env::var(BASE_URL_VAR).unwrap_or_else(|_| BASE_URL.to_string());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    pub fn year(mut self, year: u16) -> Self {
+        self.year = year;
+        self
+    }
+
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Downloads a day's input, or `None` if the puzzle isn't unlocked yet.
+    pub fn input(&self, day: usize) -> Result<Option<String>> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, self.year, day);
+        self.send(self.agent.get(&url), None)
+    }
+
+    /// A day's input as `dayNN.txt` in `dir`, downloading it only if it
+    /// isn't there yet or is empty.
+    pub fn fetch(&self, day: usize, dir: &Path) -> Result<Fetched> {
+        let path = dir.join(file_name(day, None));
+        if fs::metadata(&path).is_ok_and(|file| file.len() > 0) {
+            return Ok(Fetched::Cached(path));
+        }
+        let Some(input) = self.input(day)? else {
+            return Ok(Fetched::Locked);
+        };
+        fs::create_dir_all(dir)
+            .and_then(|_| fs::write(&path, input))
+            .map_err(|e| Error::Input {
+                source: path.display().to_string(),
+                reason: e.to_string(),
+            })?;
+        Ok(Fetched::Downloaded(path))
+    }
+
+    /// Submits `answer` for a part of a day.
+    pub fn submit(&self, day: usize, part: u8, answer: &str) -> Result<Verdict> {
+        let url = format!("{}/{}/day/{}/answer", self.base_url, self.year, day);
+        let level = part.to_string();
+        let form = [("level", level.as_str()), ("answer", answer)];
+        match self.send(self.agent.post(&url), Some(&form))? {
+            Some(page) => Ok(Verdict::parse(&page)),
+            None => Err(Error::Input {
+                source: url,
+                reason: "not found; is the puzzle unlocked yet?".to_string(),
+            }),
+        }
+    }
+
+    /// Sends a request, with `form` as its body if there is one, once the
+    /// interval since the last request has passed. Reads the body of a
+    /// successful reply, or `None` for a page that isn't there yet.
+    fn send(
+        &self,
+        request: ureq::Request,
+        form: Option<&[(&str, &str)]>,
+    ) -> Result<Option<String>> {
+        if let Some(last) = self.last.get() {
+            thread::sleep(self.interval.saturating_sub(last.elapsed()));
+        }
+        self.last.set(Some(Instant::now()));
+
+        let url = request.url().to_string();
+        let failed = |reason: String| Error::Input {
+            source: url.clone(),
+            reason,
+        };
+        let request = request.set("Cookie", &format!("session={}", self.session));
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map(Some)
+                .map_err(|e| failed(e.to_string())),
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(ureq::Error::Status(400 | 500, _)) => {
+                Err(failed(format!("refused; check {} is current", SESSION_VAR)))
+            }
+            Err(ureq::Error::Status(status, response)) => {
+                Err(failed(format!("{} {}", status, response.status_text())))
+            }
+            Err(e) => Err(failed(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{self, Receiver};
+
+    use tiny_http::{Response, Server};
+
+    use super::*;
+
+    /// A request as the stand-in site saw it.
+    #[derive(Debug)]
+    struct Seen {
+        method: String,
+        url: String,
+        cookie: Option<String>,
+        body: String,
+    }
+
+    /// Stands in for the site on a free port, answering each request with
+    /// `reply` for its URL. Returns the base URL and what was requested.
+    fn site(reply: fn(&str) -> (u16, &'static str)) -> (String, Receiver<Seen>) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("Cookie"))
+                    .map(|header| header.value.to_string());
+                let (status, text) = reply(request.url());
+                let _ = tx.send(Seen {
+                    method: request.method().to_string(),
+                    url: request.url().to_string(),
+                    cookie,
+                    body,
+                });
+                let _ = request.respond(Response::from_string(text).with_status_code(status));
+            }
+        });
+        (url, rx)
+    }
+
+    fn answers(url: &str) -> (u16, &'static str) {
+        match url {
+            "/2024/day/1/input" => (200, "3   4\n4   3\n"),
+            "/2024/day/1/answer" => (
+                200,
+                "<main><article><p>That's not the right answer; \
+                 your answer is too low.</p></article></main>",
+            ),
+            _ => (404, "404 Not Found"),
+        }
+    }
+
+    #[test]
+    fn fetch() {
+        let (url, seen) = site(answers);
+        let client = Client::new(&url, "abc123\n").interval(Duration::ZERO);
+        let dir = env::temp_dir().join(format!("aoc2024-client-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        let path = dir.join("day01.txt");
+        assert_eq!(
+            Fetched::Downloaded(path.clone()),
+            client.fetch(1, &dir).unwrap()
+        );
+        assert_eq!("3   4\n4   3\n", fs::read_to_string(&path).unwrap());
+        let request = seen.recv().unwrap();
+        assert_eq!("GET", request.method);
+        assert_eq!("/2024/day/1/input", request.url);
+        assert_eq!(Some("session=abc123"), request.cookie.as_deref());
+
+        // Already downloaded, so the site isn't asked again
+        assert_eq!(Fetched::Cached(path), client.fetch(1, &dir).unwrap());
+        assert_eq!(Fetched::Locked, client.fetch(2, &dir).unwrap());
+        assert!(!dir.join("day02.txt").exists());
+        assert_eq!("/2024/day/2/input", seen.recv().unwrap().url);
+        assert!(seen.try_recv().is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn submit() {
+        let (url, seen) = site(answers);
+        let client = Client::new(&url, "abc123").interval(Duration::ZERO);
+        assert_eq!(
+            Verdict::Incorrect(Some("too low".to_string())),
+            client.submit(1, 2, "31").unwrap()
+        );
+        let request = seen.recv().unwrap();
+        assert_eq!("POST", request.method);
+        assert_eq!("level=2&answer=31", request.body);
+    }
+
+    #[test]
+    fn rate_limit() {
+        let (url, _seen) = site(answers);
+        let interval = Duration::from_millis(100);
+        let client = Client::new(&url, "abc123").interval(interval);
+        let start = Instant::now();
+        for _ in 0..3 {
+            client.input(1).unwrap();
+        }
+        assert!(start.elapsed() >= 2 * interval);
+    }
+
+    #[test]
+    fn verdicts() {
+        let page = |text| format!("<article><p>{}</p></article>", text);
+        assert_eq!(
+            Verdict::Correct,
+            Verdict::parse(&page(
+                "That's the right answer! You are <em>one gold star</em> closer."
+            ))
+        );
+        assert_eq!(
+            Verdict::Incorrect(None),
+            Verdict::parse(&page("That's not the right answer."))
+        );
+        assert_eq!(
+            Verdict::Wait(
+                "You gave an answer too recently. You have 42s left to wait.".to_string()
+            ),
+            Verdict::parse(&page(
+                "You gave an answer too recently. You have 42s left to wait."
+            ))
+        );
+        assert_eq!(
+            Verdict::WrongLevel,
+            Verdict::parse(&page("You don't seem to be solving the right level."))
+        );
+    }
+}
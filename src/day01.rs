@@ -1,28 +1,32 @@
-#![allow(dead_code)]
-use std::{collections::HashMap, fs::read_to_string};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 
-struct Lists {
+use crate::{
+    error::Result,
+    parse::{lines, next, number, ParseError},
+    solution::{Answer, Solution},
+};
+
+pub struct Lists {
     fst: Vec<u64>,
     snd: Vec<u64>,
 }
 
 impl Lists {
-    fn new(input: &str) -> Self {
-        let (fst, snd) = input.lines().fold(
-            (Vec::<u64>::new(), Vec::<u64>::new()),
-            |(mut fst, mut snd), x| {
-                let data = x.split_whitespace().collect::<Vec<_>>();
-                fst.push(data[0].parse().unwrap());
-                snd.push(data[1].parse().unwrap());
-                (fst, snd)
-            },
-        );
-        Self { fst, snd }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let (fst, snd) = lines(input, |line| {
+            let mut data = line.split_whitespace();
+            let fst = number::<u64>(line, next(line, &mut data, "number")?)?;
+            let snd = number::<u64>(line, next(line, &mut data, "number")?)?;
+            Ok((fst, snd))
+        })?
+        .into_iter()
+        .unzip();
+        Ok(Self { fst, snd })
     }
 
-    fn total_distance(&self) -> u64 {
+    pub fn total_distance(&self) -> u64 {
         self.fst
             .iter()
             .sorted()
@@ -31,7 +35,7 @@ impl Lists {
             .sum()
     }
 
-    fn similarity_score(&self) -> u64 {
+    pub fn similarity_score(&self) -> u64 {
         let frequencies = self
             .snd
             .iter()
@@ -46,28 +50,37 @@ impl Lists {
     }
 }
 
-pub fn run() {
-    let input = read_to_string("inputs/day01.txt").unwrap();
-    let lists = Lists::new(&input);
-    println!("Total distance: {}", lists.total_distance());
-    println!("Similarity score: {}", lists.similarity_score());
+impl Solution for Lists {
+    const DAY: usize = 1;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Lists::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.total_distance().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.similarity_score().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs::read_to_string;
+    use crate::input::get_variant;
 
     use super::Lists;
 
     #[test]
     fn total_distance() {
-        let lists = Lists::new(&read_to_string("inputs/day01_small.txt").unwrap());
+        let lists = Lists::new(&get_variant(1, "small").unwrap()).unwrap();
         assert_eq!(11, lists.total_distance())
     }
 
     #[test]
     fn similarity_score() {
-        let lists = Lists::new(&read_to_string("inputs/day01_small.txt").unwrap());
+        let lists = Lists::new(&get_variant(1, "small").unwrap()).unwrap();
         assert_eq!(31, lists.similarity_score())
     }
 }
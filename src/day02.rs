@@ -1,28 +1,53 @@
-#![allow(dead_code)]
-use std::fs::read_to_string;
+use crate::{
+    error::Result,
+    parse::{lines, number, ParseError},
+    solution::{Answer, Solution},
+};
 
-use itertools::Itertools;
-
-struct UnusualData {
+pub struct UnusualData {
     reports: Vec<Report>,
 }
 
 impl UnusualData {
-    fn new(input: &str) -> Self {
-        let reports = input.lines().map(|x| Report::new(x)).collect_vec();
-        Self { reports }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let reports = lines(input, Report::new)?;
+        Ok(Self { reports })
     }
 
-    fn count_safe_reports(&self) -> usize {
+    pub fn count_safe_reports(&self) -> usize {
         self.reports.iter().filter(|x| x.safe()).count()
     }
 
-    fn count_safe_reports_with_problem_dampener(&self) -> usize {
+    pub fn count_safe_reports_with_problem_dampener(&self) -> usize {
         self.reports
             .iter()
             .filter(|x| x.problem_dampener_safe())
             .count()
     }
+
+    /// The levels of report `n`, counting from 1.
+    pub fn report(&self, n: usize) -> Option<&[i64]> {
+        let report = self.reports.get(n.checked_sub(1)?)?;
+        Some(&report.levels)
+    }
+
+    /// Whether report `n` is safe without the problem dampener.
+    pub fn safe(&self, n: usize) -> bool {
+        n.checked_sub(1)
+            .and_then(|i| self.reports.get(i))
+            .is_some_and(|report| report.safe())
+    }
+
+    /// Reports that are unsafe, but safe once the problem dampener removes a
+    /// level, with the position of that level. Both count from 1.
+    pub fn dampened_reports(&self) -> Vec<(usize, usize)> {
+        self.reports
+            .iter()
+            .enumerate()
+            .filter(|(_, report)| !report.safe())
+            .filter_map(|(i, report)| Some((i + 1, report.removable_level()? + 1)))
+            .collect()
+    }
 }
 
 struct Report {
@@ -30,12 +55,12 @@ struct Report {
 }
 
 impl Report {
-    fn new(input: &str) -> Self {
+    fn new(input: &str) -> Result<Self, ParseError> {
         let levels = input
             .split_whitespace()
-            .map(|x| x.parse::<i64>().unwrap())
-            .collect_vec();
-        Self { levels }
+            .map(|x| number(input, x))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { levels })
     }
 
     fn safe(&self) -> bool {
@@ -56,43 +81,67 @@ impl Report {
             .is_some()
     }
 
-    fn inner_safe(levels: &Vec<i64>) -> bool {
+    /// The first level whose removal makes the report safe.
+    fn removable_level(&self) -> Option<usize> {
+        (0..self.levels.len()).find(|i| {
+            let mut levels = self.levels.clone();
+            levels.remove(*i);
+            Report::inner_safe(&levels)
+        })
+    }
+
+    fn inner_safe(levels: &[i64]) -> bool {
         let increasing = levels.windows(2).find(|x| x[0] >= x[1]).is_none();
         let decreasing = levels.windows(2).find(|x| x[0] <= x[1]).is_none();
         let good_diff = levels
             .windows(2)
             .find(|x| {
                 let diff = x[0].abs_diff(x[1]);
-                diff < 1 || diff > 3
+                !(1..=3).contains(&diff)
             })
             .is_none();
         (increasing ^ decreasing) && good_diff
     }
 }
 
-pub fn run() {
-    let input = read_to_string("inputs/day02.txt").unwrap();
-    let ud = UnusualData::new(&input);
-    println!("Safe report count: {}", ud.count_safe_reports());
-    println!(
-        "Safe report count with problem dampener: {}",
-        ud.count_safe_reports_with_problem_dampener()
-    );
+impl Solution for UnusualData {
+    const DAY: usize = 2;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(UnusualData::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.count_safe_reports().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.count_safe_reports_with_problem_dampener().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use super::*;
 
     #[test]
     fn count_safe_reports() {
-        let ud = UnusualData::new(&read_to_string("inputs/day02_small.txt").unwrap());
+        let ud = UnusualData::new(&get_variant(2, "small").unwrap()).unwrap();
         assert_eq!(2, ud.count_safe_reports())
     }
 
     #[test]
     fn problem_dampener_safe_reports() {
-        let ud = UnusualData::new(&read_to_string("inputs/day02_small.txt").unwrap());
+        let ud = UnusualData::new(&get_variant(2, "small").unwrap()).unwrap();
         assert_eq!(4, ud.count_safe_reports_with_problem_dampener())
     }
+
+    #[test]
+    fn malformed_level() {
+        let err = UnusualData::new("1 2 3\n4 5- 6\n").err().unwrap();
+        assert_eq!((2, 3), (err.line, err.column));
+        assert_eq!("`5-`", err.found);
+    }
 }
@@ -1,20 +1,23 @@
-#![allow(dead_code)]
-use std::fs::read_to_string;
-
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use crate::{
+    error::Result,
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
+
 lazy_static! {
     static ref RE: Regex = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)|do\(\)|don't\(\)").unwrap();
 }
 
-struct Computer {
+pub struct Computer {
     memory: Vec<Instruction>,
 }
 
 impl Computer {
-    fn new(input: &str) -> Self {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
         let memory = RE
             .captures_iter(input)
             .map(|x| {
@@ -30,21 +33,20 @@ impl Computer {
                 }
             })
             .collect_vec();
-        Self { memory }
+        Ok(Self { memory })
     }
 
-    fn sum_multiplications(&self) -> i64 {
+    pub fn sum_multiplications(&self) -> i64 {
         self.memory
             .iter()
-            .filter(|x| matches!(x, Instruction::Mul(_)))
-            .map(|x| match x {
-                Instruction::Mul(v) => v,
-                _ => unreachable!(),
+            .filter_map(|x| match x {
+                Instruction::Mul(v) => Some(v),
+                _ => None,
             })
             .sum()
     }
 
-    fn sum_enabled_multiplications(&self) -> i64 {
+    pub fn sum_enabled_multiplications(&self) -> i64 {
         let mut sum = 0;
         let mut enabled = true;
 
@@ -70,26 +72,37 @@ enum Instruction {
     Mul(i64),
 }
 
-pub fn run() {
-    let input = read_to_string("inputs/day03.txt").unwrap();
-    let computer = Computer::new(&input);
-    println!("Multiplications sum: {}", computer.sum_multiplications());
-    println!("Enabled multiplications sum: {}", computer.sum_enabled_multiplications())
+impl Solution for Computer {
+    const DAY: usize = 3;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Computer::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.sum_multiplications().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.sum_enabled_multiplications().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use super::*;
 
     #[test]
     fn sum_multiplications() {
-        let computer = Computer::new(&read_to_string("inputs/day03_small.txt").unwrap());
+        let computer = Computer::new(&get_variant(3, "small").unwrap()).unwrap();
         assert_eq!(161, computer.sum_multiplications())
     }
 
     #[test]
     fn enabled_multiplications() {
-        let computer = Computer::new(&read_to_string("inputs/day03_small2.txt").unwrap());
+        let computer = Computer::new(&get_variant(3, "small2").unwrap()).unwrap();
         assert_eq!(48, computer.sum_enabled_multiplications())
     }
 }
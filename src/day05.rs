@@ -1,40 +1,41 @@
-#![allow(dead_code)]
-use std::{
-    collections::{HashMap, HashSet},
-    fs::read_to_string,
-};
+use std::collections::{HashMap, HashSet};
 
-use itertools::Itertools;
+use crate::{
+    error::Result,
+    parse::{lines, next, number, ParseError},
+    solution::{Answer, Solution},
+};
 
 #[derive(Debug)]
-struct Printer {
+pub struct Printer {
     rules: HashMap<u32, HashSet<u32>>,
     updates: Vec<Update>,
 }
 
 impl Printer {
-    fn new(input: &str) -> Self {
-        let data = input.split("\n\n").collect_vec();
-        let rules = data[0]
-            .lines()
-            .map(|x| {
-                let mut nums = x.split("|");
-                (
-                    nums.next().unwrap().parse().unwrap(),
-                    nums.next().unwrap().parse().unwrap(),
-                )
-            })
-            .fold(HashMap::<_, HashSet<_>>::new(), |mut acc, (fst, snd)| {
-                acc.entry(fst).or_default().insert(snd);
-                acc
-            });
-
-        let updates = data[1].lines().map(|x| Update::new(x)).collect_vec();
-
-        Self { rules, updates }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let (rules, updates) = input
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::eof(input, "blank line before the updates"))?;
+        let rules = lines(rules, |line| {
+            let mut nums = line.split("|");
+            Ok((
+                number(line, next(line, &mut nums, "number")?)?,
+                number(line, next(line, &mut nums, "`|`")?)?,
+            ))
+        })?
+        .into_iter()
+        .fold(HashMap::<_, HashSet<_>>::new(), |mut acc, (fst, snd)| {
+            acc.entry(fst).or_default().insert(snd);
+            acc
+        });
+
+        let updates = lines(updates, Update::new).map_err(|e| e.within(input, updates))?;
+
+        Ok(Self { rules, updates })
     }
 
-    fn correct_sum(&self) -> u32 {
+    pub fn correct_sum(&self) -> u32 {
         self.updates
             .iter()
             .filter(|x| x.correct(&self.rules))
@@ -42,7 +43,7 @@ impl Printer {
             .sum()
     }
 
-    fn incorrect_sum(&self) -> u32 {
+    pub fn incorrect_sum(&self) -> u32 {
         self.updates
             .iter()
             .filter(|x| !x.correct(&self.rules))
@@ -57,27 +58,25 @@ struct Update {
 }
 
 impl Update {
-    fn new(input: &str) -> Self {
-        let pages = input.split(",").map(|x| x.parse().unwrap()).collect_vec();
-        Self { pages }
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let pages = input
+            .split(",")
+            .map(|x| number(input, x))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { pages })
     }
 
     fn correct(&self, rules: &HashMap<u32, HashSet<u32>>) -> bool {
         let page_set = self.pages.iter().collect::<HashSet<_>>();
 
-        !rules
-            .keys()
-            .into_iter()
-            .filter(|x| page_set.contains(x))
-            .any(|x| {
-                let after = &rules[x];
-                self.pages
-                    .iter()
-                    .filter(|y| *y == x || after.contains(*y))
-                    .next()
-                    .unwrap()
-                    != x
-            })
+        !rules.keys().filter(|x| page_set.contains(x)).any(|x| {
+            let after = &rules[x];
+            self.pages
+                .iter()
+                .find(|y| *y == x || after.contains(*y))
+                .unwrap()
+                != x
+        })
     }
 
     fn middle_page(&self) -> u32 {
@@ -88,14 +87,14 @@ impl Update {
         let mut pages = self.pages.clone();
         let page_set = self.pages.iter().collect::<HashSet<_>>();
 
-        for left in rules.keys().into_iter().filter(|x| page_set.contains(x)) {
+        for left in rules.keys().filter(|x| page_set.contains(x)) {
             let mut sequence = pages
                 .iter()
                 .enumerate()
                 .filter(|(_, x)| *x == left || rules[left].contains(x));
             let fst = sequence.next().unwrap();
             if fst.1 != left {
-                let old_idx = sequence.filter(|(_, x)| *x == left).next().unwrap().0 + 1;
+                let old_idx = sequence.find(|(_, x)| *x == left).unwrap().0 + 1;
                 pages.insert(fst.0, *left);
                 pages.remove(old_idx);
             }
@@ -105,26 +104,37 @@ impl Update {
     }
 }
 
-pub fn run() {
-    let input = read_to_string("inputs/day05.txt").unwrap();
-    let printer = Printer::new(&input);
-    println!("Correct sum: {}", printer.correct_sum());
-    println!("Incorrect sum: {}", printer.incorrect_sum())
+impl Solution for Printer {
+    const DAY: usize = 5;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Printer::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.correct_sum().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.incorrect_sum().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use super::*;
 
     #[test]
     fn correct_sum() {
-        let printer = Printer::new(&read_to_string("inputs/day05_small.txt").unwrap());
+        let printer = Printer::new(&get_variant(5, "small").unwrap()).unwrap();
         assert_eq!(143, printer.correct_sum())
     }
 
     #[test]
     fn incorrect_sum() {
-        let printer = Printer::new(&read_to_string("inputs/day05_small.txt").unwrap());
+        let printer = Printer::new(&get_variant(5, "small").unwrap()).unwrap();
         assert_eq!(123, printer.incorrect_sum())
     }
 }
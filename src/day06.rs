@@ -1,123 +1,151 @@
-#![allow(dead_code)]
 use itertools::Itertools;
 use rayon::prelude::*;
-use std::{
-    collections::{HashMap, HashSet},
-    fs::read_to_string,
-    isize,
+use std::collections::HashSet;
+use tracing::instrument;
+
+use crate::{
+    animate::{Animate, Frame, Frames},
+    cancel,
+    error::Result,
+    geometry::{Direction, Point},
+    parse::ParseError,
+    solution::{Answer, Part, Solution},
+    utils::Grid,
 };
 
-struct Map {
-    grid: HashMap<(isize, isize), Space>,
-    start: (isize, isize),
-    y_max: isize,
-    x_max: isize,
+pub struct Map {
+    grid: Grid<Space>,
+    start: Point,
 }
 
 impl Map {
-    fn new(input: &str) -> Self {
-        let mut start = (0, 0);
-        let mut grid = HashMap::new();
-        for (y, line) in input.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let space = match c {
-                    '#' => Space::Obstacle,
-                    '^' => {
-                        start = (y as isize, x as isize);
-                        Space::Ground
-                    }
-                    _ => Space::Ground,
-                };
-                grid.insert((y as isize, x as isize), space);
-            }
-        }
-        let y_max = input.lines().count() as isize;
-        let x_max = input.lines().next().unwrap().chars().count() as isize;
-        Self {
-            grid,
-            start,
-            y_max,
-            x_max,
-        }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let chars = Grid::parse(input, "`.`, `#` or `^`", |c| {
+            matches!(c, '.' | '#' | '^').then_some(c)
+        })?;
+        let start = chars
+            .find(&'^')
+            .ok_or_else(|| ParseError::eof(input, "guard `^`"))?;
+        let grid = chars.map(|c| match c {
+            '#' => Space::Obstacle,
+            _ => Space::Ground,
+        });
+        Ok(Self { grid, start })
     }
 
-    fn distinct_positions(&self) -> usize {
+    pub fn distinct_positions(&self) -> usize {
         self.visited().len()
     }
 
-    fn different_obstructions(&self) -> usize {
-        // (0..self.y_max)
-        //     .flat_map(|y| (0..self.x_max).map(move |x| (y, x)))
-        //     .filter(|pos| *pos != self.start && self.grid.get(pos) != Some(&Space::Obstacle))
-        //     .map(|pos| self.cycles(pos))
-        //     .filter(|x| *x)
-        //     .count()
+    pub fn different_obstructions(&self) -> usize {
+        let token = cancel::current();
         self.visited()
             .into_iter()
             .collect_vec()
             .into_par_iter()
             .filter(|pos| *pos != self.start)
-            .map(|pos| self.cycles(pos))
+            .map(|pos| cancel::within(token, || self.cycles(pos)))
             .filter(|x| *x)
             .count()
     }
 
-    fn visited(&self) -> HashSet<(isize, isize)> {
-        self.walk(HashSet::new(), Direction::North, self.start)
+    /// [`Map::different_obstructions`] trying every open position instead of
+    /// only those the guard walks through.
+    pub fn different_obstructions_brute_force(&self) -> usize {
+        self.grid
+            .iter()
+            .filter(|(pos, space)| *pos != self.start && **space == Space::Ground)
+            .filter(|(pos, _)| self.cycles(*pos))
+            .count()
     }
 
-    fn walk(
-        &self,
-        mut visited: HashSet<(isize, isize)>,
-        direction: Direction,
-        position: (isize, isize),
-    ) -> HashSet<(isize, isize)> {
-        if self.grid.get(&position).is_none() {
-            return visited;
-        }
-        visited.insert(position);
-
-        let facing = Map::facing(direction, position);
-        if self.grid.get(&facing) == Some(&Space::Obstacle) {
-            return self.walk(visited, direction.rotate(), position);
+    /// Positions the guard walks through before leaving the map. A guard
+    /// stuck in a loop walks until the run is cancelled.
+    #[instrument(skip_all)]
+    fn visited(&self) -> HashSet<Point> {
+        let mut visited = HashSet::new();
+        let mut direction = Direction::Up;
+        let mut position = self.start;
+        while self.grid.contains(position) {
+            cancel::checkpoint();
+            visited.insert(position);
+            let facing = position + direction;
+            if self.grid.get(facing) == Some(&Space::Obstacle) {
+                direction = direction.clockwise();
+            } else {
+                position = facing;
+            }
         }
-        return self.walk(visited, direction, facing);
+        visited
     }
 
-    fn cycles(&self, obstacle: (isize, isize)) -> bool {
-        return self.inner_cycles(&mut HashSet::new(), obstacle, Direction::North, self.start);
+    fn cycles(&self, obstacle: Point) -> bool {
+        cancel::checkpoint();
+        let mut visited = self.grid.map(|_| 0);
+        self.inner_cycles(&mut visited, obstacle, Direction::Up, self.start)
     }
 
+    /// `visited` holds a bit per direction the guard has left each position
+    /// in.
     fn inner_cycles(
         &self,
-        visited: &mut HashSet<((isize, isize), Direction)>,
-        obstacle: (isize, isize),
+        visited: &mut Grid<u8>,
+        obstacle: Point,
         direction: Direction,
-        position: (isize, isize),
+        position: Point,
     ) -> bool {
-        if self.grid.get(&position).is_none() {
+        let Some(seen) = visited.get_mut(position) else {
             return false;
-        }
-        if visited.contains(&(position, direction)) {
+        };
+        let bit = 1 << direction as u8;
+        if *seen & bit != 0 {
             return true;
         }
-        visited.insert((position, direction));
+        *seen |= bit;
 
-        let facing = Map::facing(direction, position);
-        if self.grid.get(&facing) == Some(&Space::Obstacle) || facing == obstacle {
-            return self.inner_cycles(visited, obstacle, direction.rotate(), position);
+        let facing = position + direction;
+        if self.grid.get(facing) == Some(&Space::Obstacle) || facing == obstacle {
+            return self.inner_cycles(visited, obstacle, direction.clockwise(), position);
         }
         self.inner_cycles(visited, obstacle, direction, facing)
     }
+}
 
-    fn facing(direction: Direction, position: (isize, isize)) -> (isize, isize) {
-        let (y, x) = position;
-        match direction {
-            Direction::North => (y - 1, x),
-            Direction::East => (y, x + 1),
-            Direction::South => (y + 1, x),
-            Direction::West => (y, x - 1),
-        }
+impl Animate for Map {
+    /// The guard's patrol, one step or turn at a time, until they leave.
+    fn frames(self, _: Part) -> Frames {
+        let mut visited = HashSet::new();
+        let mut guard = Some((self.start, Direction::Up));
+        let mut step = 0;
+        Box::new(std::iter::from_fn(move || {
+            let (position, direction) = guard?;
+            visited.insert(position);
+            let mut map = self.grid.map(|space| match space {
+                Space::Obstacle => '#',
+                Space::Ground => '.',
+            });
+            for p in &visited {
+                map[*p] = 'X';
+            }
+            map[position] = direction.arrow();
+            let status = format!(
+                "step {}, guard at {},{} facing {:?}, {} positions visited",
+                step,
+                position.x,
+                position.y,
+                direction,
+                visited.len()
+            );
+
+            let facing = position + direction;
+            guard = match self.grid.get(facing) {
+                Some(Space::Obstacle) => Some((position, direction.clockwise())),
+                Some(Space::Ground) => Some((facing, direction)),
+                None => None,
+            };
+            step += 1;
+            Some(Frame { map, status })
+        }))
     }
 }
 
@@ -127,57 +155,49 @@ enum Space {
     Obstacle,
 }
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
+impl Solution for Map {
+    const DAY: usize = 6;
 
-impl Direction {
-    fn rotate(&self) -> Direction {
-        match self {
-            Self::North => Self::East,
-            Self::East => Self::South,
-            Self::South => Self::West,
-            Self::West => Self::North,
-        }
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Map::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.distinct_positions().into()
     }
-}
 
-pub fn run() {
-    let input = read_to_string("inputs/day06.txt").unwrap();
-    let map = Map::new(&input);
-    println!("Distinct positions: {}", map.distinct_positions());
-    println!("Different obstructions: {}", map.different_obstructions())
+    fn part2(&self) -> Answer {
+        self.different_obstructions().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use super::*;
 
     #[test]
     fn distinct_positions() {
-        let map = Map::new(&read_to_string("inputs/day06_small.txt").unwrap());
+        let map = Map::new(&get_variant(6, "small").unwrap()).unwrap();
         assert_eq!(41, map.distinct_positions())
     }
 
     #[test]
     fn positive_cycle() {
-        let map = Map::new(&read_to_string("inputs/day06_small.txt").unwrap());
-        assert_eq!(true, map.cycles((6, 3)))
+        let map = Map::new(&get_variant(6, "small").unwrap()).unwrap();
+        assert!(map.cycles(Point::new(6, 3)))
     }
 
     #[test]
     fn negative_cycle() {
-        let map = Map::new(&read_to_string("inputs/day06_small.txt").unwrap());
-        assert_eq!(false, map.cycles((6, 2)))
+        let map = Map::new(&get_variant(6, "small").unwrap()).unwrap();
+        assert!(!map.cycles(Point::new(6, 2)))
     }
 
     #[test]
     fn different_obstructions() {
-        let map = Map::new(&read_to_string("inputs/day06_small.txt").unwrap());
+        let map = Map::new(&get_variant(6, "small").unwrap()).unwrap();
         assert_eq!(6, map.different_obstructions())
     }
 }
@@ -1,25 +1,20 @@
-#![allow(dead_code)]
-use std::fs::read_to_string;
+use crate::{
+    error::Result,
+    parse::{lines, number, ParseError},
+    solution::{Answer, Solution},
+};
 
-use itertools::Itertools;
-use lazy_static::lazy_static;
-use regex::Regex;
-
-lazy_static! {
-    static ref RE: Regex = Regex::new(r"\d+").unwrap();
-}
-
-struct Bridge {
+pub struct Bridge {
     equations: Vec<Equation>,
 }
 
 impl Bridge {
-    fn new(input: &str) -> Self {
-        let equations = input.lines().map(|x| Equation::new(x)).collect_vec();
-        Self { equations }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let equations = lines(input, Equation::new)?;
+        Ok(Self { equations })
     }
 
-    fn total_calibration(&self) -> i64 {
+    pub fn total_calibration(&self) -> i64 {
         self.equations
             .iter()
             .filter(|x| x.possible())
@@ -27,7 +22,7 @@ impl Bridge {
             .sum()
     }
 
-    fn total_calibration_concat(&self) -> i64 {
+    pub fn total_calibration_concat(&self) -> i64 {
         self.equations
             .iter()
             .filter(|x| x.possible_concat())
@@ -42,13 +37,19 @@ struct Equation {
 }
 
 impl Equation {
-    fn new(input: &str) -> Self {
-        let mut nums = RE
-            .find_iter(input)
-            .map(|x| x.as_str().parse::<i64>().unwrap());
-        let target = nums.next().unwrap();
-        let values = nums.collect_vec();
-        Self { target, values }
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let (target, values) = input
+            .split_once(":")
+            .ok_or_else(|| ParseError::eof(input, "`:`"))?;
+        let target = number(input, target)?;
+        let values = values
+            .split_whitespace()
+            .map(|x| number(input, x))
+            .collect::<Result<Vec<_>, _>>()?;
+        if values.is_empty() {
+            return Err(ParseError::eof(input, "number"));
+        }
+        Ok(Self { target, values })
     }
 
     fn possible(&self) -> bool {
@@ -94,29 +95,37 @@ impl Equation {
     }
 }
 
-pub fn run() {
-    let input = read_to_string("inputs/day07.txt").unwrap();
-    let bridge = Bridge::new(&input);
-    println!("Total calibration result: {}", bridge.total_calibration());
-    println!(
-        "Total calibration result with concat: {}",
-        bridge.total_calibration_concat()
-    )
+impl Solution for Bridge {
+    const DAY: usize = 7;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Bridge::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.total_calibration().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.total_calibration_concat().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use super::*;
 
     #[test]
     fn total_calibration() {
-        let bridge = Bridge::new(&read_to_string("inputs/day07_small.txt").unwrap());
+        let bridge = Bridge::new(&get_variant(7, "small").unwrap()).unwrap();
         assert_eq!(3749, bridge.total_calibration())
     }
 
     #[test]
     fn total_calibration_concat() {
-        let bridge = Bridge::new(&read_to_string("inputs/day07_small.txt").unwrap());
+        let bridge = Bridge::new(&get_variant(7, "small").unwrap()).unwrap();
         assert_eq!(11387, bridge.total_calibration_concat())
     }
 }
@@ -1,31 +1,42 @@
-#![allow(dead_code)]
-use std::fs::read_to_string;
-
 use itertools::Itertools;
 
-struct Computer {
+use crate::{
+    error::Result,
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
+
+pub struct Computer {
     disk: Vec<Blop>,
 }
 
 impl Computer {
-    fn new(input: &str) -> Self {
-        let mut id = 0;
-        let disk = input
-            .trim()
-            .chars()
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let digits = input.trim();
+        let sizes = digits
+            .char_indices()
+            .map(|(i, c)| {
+                c.to_digit(10)
+                    .map(|d| d as usize)
+                    .ok_or_else(|| ParseError::new(input, &digits[i..i + c.len_utf8()], "digit"))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if !sizes.iter().step_by(2).any(|x| *x > 0) {
+            return Err(ParseError::eof(input, "file with at least one block"));
+        }
+        let disk = sizes
             .chunks(2)
-            .into_iter()
-            .flat_map(|mut x| {
+            .enumerate()
+            .flat_map(|(id, x)| {
                 let file = Blop {
                     kind: BlopKind::File(id),
-                    blocks: x.next().unwrap().to_digit(10).unwrap() as usize,
+                    blocks: x[0],
                 };
-                id += 1;
 
-                if let Some(free) = x.next() {
+                if let Some(free) = x.get(1) {
                     let free = Blop {
                         kind: BlopKind::Free,
-                        blocks: free.to_digit(10).unwrap() as usize,
+                        blocks: *free,
                     };
                     Vec::from([file, free])
                 } else {
@@ -34,10 +45,10 @@ impl Computer {
             })
             .filter(|x| x.blocks > 0)
             .collect_vec();
-        Self { disk }
+        Ok(Self { disk })
     }
 
-    fn checksum(&self) -> usize {
+    pub fn checksum(&self) -> usize {
         let mut uncompressed = Vec::<BlopKind>::new();
         for x in self.disk.iter() {
             for _ in 0..x.blocks {
@@ -74,12 +85,11 @@ impl Computer {
             .sum()
     }
 
-    fn defragmented_checksum(&self) -> usize {
+    pub fn defragmented_checksum(&self) -> usize {
         let max_id = match self
             .disk
             .iter()
-            .filter(|x| matches!(x.kind, BlopKind::File(_)))
-            .last()
+            .rfind(|x| matches!(x.kind, BlopKind::File(_)))
             .unwrap()
             .kind
         {
@@ -158,28 +168,37 @@ enum BlopKind {
     File(usize),
 }
 
-pub fn run() {
-    let input = read_to_string("inputs/day09.txt").unwrap();
-    let c = Computer::new(&input);
-    println!("Checksum: {}", c.checksum());
-    println!("Defragmented checksum: {}", c.defragmented_checksum());
+impl Solution for Computer {
+    const DAY: usize = 9;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Computer::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.checksum().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.defragmented_checksum().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs::read_to_string;
+    use crate::input::get_variant;
 
     use super::Computer;
 
     #[test]
     fn checksum() {
-        let c = Computer::new(&read_to_string("inputs/day09_small.txt").unwrap());
+        let c = Computer::new(&get_variant(9, "small").unwrap()).unwrap();
         assert_eq!(1928, c.checksum())
     }
 
     #[test]
     fn defragmented_checksum() {
-        let c = Computer::new(&read_to_string("inputs/day09_small.txt").unwrap());
+        let c = Computer::new(&get_variant(9, "small").unwrap()).unwrap();
         assert_eq!(2858, c.defragmented_checksum())
     }
 }
@@ -1,52 +1,44 @@
-#![allow(dead_code)]
-use std::{
-    collections::{HashMap, HashSet},
-    fs::read_to_string,
+use std::collections::HashSet;
+
+use crate::{
+    error::Result,
+    geometry::Point,
+    parse::ParseError,
+    solution::{Answer, Solution},
+    utils::Grid,
 };
 
-struct TopographicMap {
-    map: HashMap<(isize, isize), u32>,
-    trailheads: Vec<(isize, isize)>,
+pub struct TopographicMap {
+    map: Grid<u32>,
+    trailheads: Vec<Point>,
 }
 
 impl TopographicMap {
-    fn new(input: &str) -> Self {
-        let mut map = HashMap::new();
-        let mut trailheads = Vec::new();
-
-        for (y, line) in input.lines().enumerate() {
-            for (x, d) in line.chars().map(|c| c.to_digit(10).unwrap()).enumerate() {
-                let pos = (y as isize, x as isize);
-                if d == 0 {
-                    trailheads.push(pos);
-                }
-                map.insert(pos, d);
-            }
-        }
-
-        Self { map, trailheads }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let map = Grid::parse(input, "digit", |c| c.to_digit(10))?;
+        let trailheads = map
+            .iter()
+            .filter(|(_, height)| **height == 0)
+            .map(|(pos, _)| pos)
+            .collect();
+        Ok(Self { map, trailheads })
     }
 
-    fn trailhead_score_sum(&self) -> usize {
+    pub fn trailhead_score_sum(&self) -> usize {
         self.trailheads
             .iter()
             .map(|x| self.trailhead_score(*x))
             .sum()
     }
 
-    fn trailhead_score(&self, trailhead: (isize, isize)) -> usize {
-        let mut nines = HashSet::<(isize, isize)>::new();
+    pub fn trailhead_score(&self, trailhead: Point) -> usize {
+        let mut nines = HashSet::<Point>::new();
         self.trailhead_score_helper(&mut nines, trailhead, 0);
         nines.len()
     }
 
-    fn trailhead_score_helper(
-        &self,
-        nines: &mut HashSet<(isize, isize)>,
-        pos: (isize, isize),
-        target: u32,
-    ) {
-        if self.map.get(&pos) != Some(&target) {
+    fn trailhead_score_helper(&self, nines: &mut HashSet<Point>, pos: Point, target: u32) {
+        if self.map.get(pos) != Some(&target) {
             return;
         }
         if target == 9 {
@@ -54,62 +46,68 @@ impl TopographicMap {
             return;
         }
 
-        let (y, x) = pos;
-        let target = target + 1;
-        self.trailhead_score_helper(nines, (y + 1, x), target);
-        self.trailhead_score_helper(nines, (y - 1, x), target);
-        self.trailhead_score_helper(nines, (y, x + 1), target);
-        self.trailhead_score_helper(nines, (y, x - 1), target);
+        for next in self.map.neighbours(pos) {
+            self.trailhead_score_helper(nines, next, target + 1);
+        }
     }
 
-    fn trailhead_rating_sum(&self) -> usize {
+    pub fn trailhead_rating_sum(&self) -> usize {
         self.trailheads
             .iter()
             .map(|x| self.trailhead_rating(*x))
             .sum()
     }
 
-    fn trailhead_rating(&self, trailhead: (isize, isize)) -> usize {
+    pub fn trailhead_rating(&self, trailhead: Point) -> usize {
         self.trailhead_rating_helper(trailhead, 0)
     }
 
-    fn trailhead_rating_helper(&self, pos: (isize, isize), target: u32) -> usize {
-        if self.map.get(&pos) != Some(&target) {
+    fn trailhead_rating_helper(&self, pos: Point, target: u32) -> usize {
+        if self.map.get(pos) != Some(&target) {
             return 0;
         }
         if target == 9 {
             return 1;
         }
 
-        let (y, x) = pos;
-        let target = target + 1;
-        self.trailhead_rating_helper((y + 1, x), target)
-            + self.trailhead_rating_helper((y - 1, x), target)
-            + self.trailhead_rating_helper((y, x + 1), target)
-            + self.trailhead_rating_helper((y, x - 1), target)
+        self.map
+            .neighbours(pos)
+            .map(|next| self.trailhead_rating_helper(next, target + 1))
+            .sum()
     }
 }
 
-pub fn run() {
-    let input = read_to_string("inputs/day10.txt").unwrap();
-    let tm = TopographicMap::new(&input);
-    println!("Trailhead score sum: {}", tm.trailhead_score_sum());
-    println!("Trailhead rating sum: {}", tm.trailhead_rating_sum());
+impl Solution for TopographicMap {
+    const DAY: usize = 10;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(TopographicMap::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.trailhead_score_sum().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.trailhead_rating_sum().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use super::*;
 
     #[test]
     fn trailhead_score_sum() {
-        let tm = TopographicMap::new(&read_to_string("inputs/day10_small.txt").unwrap());
+        let tm = TopographicMap::new(&get_variant(10, "small").unwrap()).unwrap();
         assert_eq!(36, tm.trailhead_score_sum())
     }
 
     #[test]
     fn trailhead_rating_sum() {
-        let tm = TopographicMap::new(&read_to_string("inputs/day10_small.txt").unwrap());
+        let tm = TopographicMap::new(&get_variant(10, "small").unwrap()).unwrap();
         assert_eq!(81, tm.trailhead_rating_sum())
     }
 }
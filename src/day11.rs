@@ -1,25 +1,31 @@
-#![allow(dead_code)]
-use std::{collections::HashMap, fs::read_to_string};
+use std::collections::HashMap;
 
-struct Pluto {
+use tracing::instrument;
+
+use crate::{
+    error::Result,
+    parse::{number, ParseError},
+    solution::{Answer, Solution},
+};
+
+pub struct Pluto {
     stones: HashMap<Stone, usize>,
 }
 
 impl Pluto {
-    fn new(input: &str) -> Self {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
         let mut stones = HashMap::new();
 
-        for stone in input
-            .split_whitespace()
-            .map(|x| Stone::new(x.parse().unwrap()))
-        {
+        for x in input.split_whitespace() {
+            let stone = Stone::new(number(input, x)?);
             *stones.entry(stone).or_default() += 1;
         }
 
-        Self { stones }
+        Ok(Self { stones })
     }
 
-    fn blink(&self, count: usize) -> usize {
+    #[instrument(skip(self))]
+    pub fn blink(&self, count: usize) -> usize {
         let mut stones = self.stones.clone();
         for _ in 0..count {
             let mut new_stones = HashMap::new();
@@ -62,11 +68,20 @@ impl Stone {
     }
 }
 
-pub fn run() {
-    let input = read_to_string("inputs/day11.txt").unwrap();
-    let pluto = Pluto::new(&input);
-    println!("Stones after 25 blinks: {}", pluto.blink(25));
-    println!("Stones after 75 blinks: {}", pluto.blink(75));
+impl Solution for Pluto {
+    const DAY: usize = 11;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Pluto::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.blink(25).into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.blink(75).into()
+    }
 }
 
 #[cfg(test)]
@@ -75,7 +90,7 @@ mod tests {
 
     #[test]
     fn blink25() {
-        let pluto = Pluto::new("125 17");
+        let pluto = Pluto::new("125 17").unwrap();
         assert_eq!(55312, pluto.blink(25))
     }
 }
@@ -1,70 +1,60 @@
-#![allow(dead_code)]
-use std::{collections::HashMap, fs::read_to_string};
-
 use itertools::Itertools;
 
-struct Garden {
-    grid: HashMap<(isize, isize), char>,
+use crate::{
+    error::Result,
+    geometry::{Direction, Point},
+    image::{self, Image, Picture},
+    parse::ParseError,
+    solution::{Answer, Solution},
+    utils::Grid,
+};
+
+pub struct Garden {
+    grid: Grid<char>,
 }
 
 impl Garden {
-    fn new(input: &str) -> Self {
-        let mut grid = HashMap::new();
-        for (y, line) in input.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let pos = (y as isize, x as isize);
-                grid.insert(pos, c);
-            }
-        }
-        Self { grid }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::from_chars(input)?;
+        Ok(Self { grid })
     }
 
-    fn price(&self) -> u32 {
-        let mut grid = self.grid.clone();
+    pub fn price(&self) -> u32 {
+        let mut visited = self.grid.map(|_| false);
         let mut price = 0;
-        while grid.len() > 0 {
-            let pos = *grid.iter().next().unwrap().0;
-            let c = grid[&pos];
-            let (perimeter, area) = self.price_inner(&mut grid, pos, c);
+        for (pos, c) in self.grid.iter() {
+            let (perimeter, area) = self.price_inner(&mut visited, pos, *c);
             price += perimeter * area;
         }
         price
     }
 
-    fn price_inner(
-        &self,
-        grid: &mut HashMap<(isize, isize), char>,
-        pos: (isize, isize),
-        target: char,
-    ) -> (u32, u32) {
-        if grid.get(&pos) != Some(&target) {
+    fn price_inner(&self, visited: &mut Grid<bool>, pos: Point, target: char) -> (u32, u32) {
+        if self.grid.get(pos) != Some(&target) || visited[pos] {
             return (0, 0);
         }
-        grid.remove(&pos);
+        visited[pos] = true;
 
-        let (y, x) = pos;
-        let directions = [(y + 1, x), (y - 1, x), (y, x + 1), (y, x - 1)];
+        let directions = pos.neighbours().collect_vec();
 
         let initial_perimeter = directions
             .iter()
-            .filter(|x| self.grid.get(x) != Some(&target))
+            .filter(|x| self.grid.get(**x) != Some(&target))
             .count() as u32;
         let results = directions
             .iter()
-            .map(|x| self.price_inner(grid, *x, target))
+            .map(|x| self.price_inner(visited, *x, target))
             .collect_vec();
         let perimeter = initial_perimeter + results.iter().map(|x| x.0).sum::<u32>();
         let area = 1 + results.iter().map(|x| x.1).sum::<u32>();
         (perimeter, area)
     }
 
-    fn price_sides(&self) -> u32 {
-        let mut grid = self.grid.clone();
+    pub fn price_sides(&self) -> u32 {
+        let mut visited = self.grid.map(|_| false);
         let mut price = 0;
-        while grid.len() > 0 {
-            let pos = *grid.iter().next().unwrap().0;
-            let c = grid[&pos];
-            let (sides, area) = self.price_sides_inner(&mut grid, pos, c);
+        for (pos, c) in self.grid.iter() {
+            let (sides, area) = self.price_sides_inner(&mut visited, pos, *c);
             price += sides as u32 * area;
         }
         price
@@ -72,78 +62,79 @@ impl Garden {
 
     fn price_sides_inner(
         &self,
-        grid: &mut HashMap<(isize, isize), char>,
-        pos: (isize, isize),
+        visited: &mut Grid<bool>,
+        pos: Point,
         target: char,
     ) -> (usize, u32) {
-        if grid.get(&pos) != Some(&target) {
+        if self.grid.get(pos) != Some(&target) || visited[pos] {
             return (0, 0);
         }
-        grid.remove(&pos);
+        visited[pos] = true;
 
-        let (y, x) = pos;
-        let directions = [(y + 1, x), (y - 1, x), (y, x + 1), (y, x - 1)];
+        let directions = pos.neighbours().collect_vec();
 
         let initial_corners = self.corners(pos, target);
         let results = directions
             .iter()
-            .map(|x| self.price_sides_inner(grid, *x, target))
+            .map(|x| self.price_sides_inner(visited, *x, target))
             .collect_vec();
         let corners = initial_corners + results.iter().map(|x| x.0).sum::<usize>();
         let area = 1 + results.iter().map(|x| x.1).sum::<u32>();
         (corners, area)
     }
 
-    fn corners(&self, pos: (isize, isize), target: char) -> usize {
-        let (y, x) = pos;
-        let surrounding = [
-            ((y, x - 1), (y - 1, x)),
-            ((y - 1, x), (y, x + 1)),
-            ((y, x + 1), (y + 1, x)),
-            ((y + 1, x), (y, x - 1)),
-        ];
-
-        let outer = surrounding
-            .iter()
-            .filter(|x| {
-                self.grid.get(&x.0) != Some(&target) && self.grid.get(&x.1) != Some(&target)
-            })
-            .count();
-        let inner = surrounding
-            .iter()
-            .filter(|a| {
-                let y_diag = [a.0 .0, a.1 .0].into_iter().find(|b| *b != y).unwrap();
-                let x_diag = [a.0 .1, a.1 .1].into_iter().find(|b| *b != x).unwrap();
-                let diag = (y_diag, x_diag);
-                self.grid.get(&a.0) == Some(&target)
-                    && self.grid.get(&a.1) == Some(&target)
-                    && self.grid.get(&diag) != Some(&target)
+    fn corners(&self, pos: Point, target: char) -> usize {
+        let same = |p: Point| self.grid.get(p) == Some(&target);
+        Direction::ALL
+            .into_iter()
+            .filter(|d| {
+                let (a, b) = (pos + *d, pos + d.clockwise());
+                let outer = !same(a) && !same(b);
+                let inner = same(a) && same(b) && !same(a + d.clockwise());
+                outer || inner
             })
-            .count();
-        outer + inner
+            .count()
+    }
+}
+
+impl Picture for Garden {
+    /// Each plot coloured by the plant growing in it.
+    fn picture(&self, scale: usize) -> Image {
+        Image::from_grid(&self.grid, scale, |plant| image::hashed_colour(plant))
     }
 }
 
-pub fn run() {
-    let input = read_to_string("inputs/day12.txt").unwrap();
-    let g = Garden::new(&input);
-    println!("Total price: {}", g.price());
-    println!("Total price counting sides: {}", g.price_sides());
+impl Solution for Garden {
+    const DAY: usize = 12;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Garden::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.price().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.price_sides().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use crate::day12::*;
 
     #[test]
     fn total_price() {
-        let g = Garden::new(&read_to_string("inputs/day12_small.txt").unwrap());
+        let g = Garden::new(&get_variant(12, "small").unwrap()).unwrap();
         assert_eq!(1930, g.price())
     }
 
     #[test]
     fn total_price_sides() {
-        let g = Garden::new(&read_to_string("inputs/day12_small.txt").unwrap());
+        let g = Garden::new(&get_variant(12, "small").unwrap()).unwrap();
         assert_eq!(1206, g.price_sides())
     }
 }
@@ -1,30 +1,32 @@
-#![allow(dead_code)]
-use std::fs::read_to_string;
-
-use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
 
+use crate::{
+    error::Result,
+    parse::{blocks, lines, next, number, ParseError},
+    solution::{Answer, Solution},
+};
+
 lazy_static! {
     static ref RE: Regex = Regex::new(r"\d+").unwrap();
 }
 
 #[derive(Clone)]
-struct Lobby {
+pub struct Lobby {
     machines: Vec<Machine>,
 }
 
 impl Lobby {
-    fn new(input: &str) -> Self {
-        let machines = input.split("\n\n").map(|x| Machine::new(x)).collect_vec();
-        Self { machines }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let machines = blocks(input, Machine::new)?;
+        Ok(Self { machines })
     }
 
-    fn fewest_tokens(&self) -> i64 {
+    pub fn fewest_tokens(&self) -> i64 {
         self.machines.iter().filter_map(|m| m.tokens()).sum()
     }
 
-    fn fewest_tokens_big(&self) -> i64 {
+    pub fn fewest_tokens_big(&self) -> i64 {
         let mut big = self.clone();
         for m in big.machines.iter_mut() {
             m.prize = (m.prize.0 + 10000000000000, m.prize.1 + 10000000000000)
@@ -41,21 +43,20 @@ struct Machine {
 }
 
 impl Machine {
-    fn new(input: &str) -> Self {
-        let data = input
-            .lines()
-            .map(|line| {
-                RE.find_iter(line)
-                    .map(|m| m.as_str().parse().unwrap())
-                    .collect_tuple()
-                    .unwrap()
-            })
-            .collect_vec();
-        Self {
-            a: data[0],
-            b: data[1],
-            prize: data[2],
-        }
+    fn new(input: &str) -> Result<Self, ParseError> {
+        let mut data = lines(input, |line| {
+            let mut ns = RE.find_iter(line).map(|m| m.as_str());
+            let x = number(line, next(line, &mut ns, "number")?)?;
+            let y = number(line, next(line, &mut ns, "number")?)?;
+            Ok((x, y))
+        })?
+        .into_iter();
+        let mut next_line = |expected| data.next().ok_or_else(|| ParseError::eof(input, expected));
+        Ok(Self {
+            a: next_line("button A")?,
+            b: next_line("button B")?,
+            prize: next_line("prize")?,
+        })
     }
 
     fn tokens(&self) -> Option<i64> {
@@ -76,20 +77,31 @@ fn determinant(a: i64, b: i64, c: i64, d: i64) -> i64 {
     a * d - b * c
 }
 
-pub fn run() {
-    let input = read_to_string("inputs/day13.txt").unwrap();
-    let lobby = Lobby::new(&input);
-    println!("Fewest tokens: {}", lobby.fewest_tokens());
-    println!("Fewest tokens big: {}", lobby.fewest_tokens_big());
+impl Solution for Lobby {
+    const DAY: usize = 13;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Lobby::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.fewest_tokens().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.fewest_tokens_big().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use super::*;
 
     #[test]
     fn fewest_tokens() {
-        let lobby = Lobby::new(&read_to_string("inputs/day13_small.txt").unwrap());
+        let lobby = Lobby::new(&get_variant(13, "small").unwrap()).unwrap();
         assert_eq!(480, lobby.fewest_tokens())
     }
 }
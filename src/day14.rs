@@ -1,28 +1,38 @@
-#![allow(dead_code)]
-use std::{collections::HashSet, fs::read_to_string};
+use std::collections::HashSet;
 
-use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
+use tracing::instrument;
+
+use crate::{
+    animate::{Animate, Frame, Frames},
+    cancel,
+    error::Result,
+    geometry::Point,
+    image::{self, Image, Picture},
+    parse::{lines, next, number, ParseError},
+    solution::{read_params, Answer, Params, Part, Solution},
+    utils::Grid,
+};
 
 lazy_static! {
     static ref RE: Regex = Regex::new(r"-{0,1}\d+").unwrap();
 }
 
-struct Bathroom {
+pub struct Bathroom {
     robots: Vec<Robot>,
     x_max: isize,
     y_max: isize,
 }
 
 impl Bathroom {
-    fn new(input: &str, x_max: isize, y_max: isize) -> Self {
-        let robots = input.lines().map(|line| Robot::new(line)).collect_vec();
-        Self {
+    pub fn new(input: &str, x_max: isize, y_max: isize) -> Result<Self, ParseError> {
+        let robots = lines(input, |line| Robot::new(line, x_max, y_max))?;
+        Ok(Self {
             robots,
             x_max,
             y_max,
-        }
+        })
     }
 
     fn pace(&self) -> Vec<Robot> {
@@ -36,7 +46,7 @@ impl Bathroom {
         robots
     }
 
-    fn safety_factor(&self) -> usize {
+    pub fn safety_factor(&self) -> usize {
         let robots = self.pace();
         let xs = self.x_max / 2;
         let xl = xs + 1;
@@ -46,7 +56,7 @@ impl Bathroom {
             robots
                 .iter()
                 .fold((0, 0, 0, 0), |(mut q1, mut q2, mut q3, mut q4), r| {
-                    let (x, y) = r.pos;
+                    let Point { x, y } = r.pos;
                     if (0..ys).contains(&y) {
                         if (xl..self.x_max).contains(&x) {
                             q1 += 1;
@@ -65,10 +75,12 @@ impl Bathroom {
         q1 * q2 * q3 * q4
     }
 
-    fn find_tree(&self) -> usize {
+    #[instrument(skip_all)]
+    pub fn find_tree(&self) -> usize {
         let mut robots = self.robots.clone();
         (0..)
             .find(|_| {
+                cancel::checkpoint();
                 let cond =
                     robots.iter().map(|r| r.pos).collect::<HashSet<_>>().len() == robots.len();
                 robots = robots
@@ -80,7 +92,8 @@ impl Bathroom {
             .unwrap()
     }
 
-    fn display(&self, seconds: usize) {
+    /// Tiles with at least one robot on them after `seconds`.
+    fn occupied(&self, seconds: usize) -> HashSet<Point> {
         let mut robots = self.robots.clone();
         for _ in 0..seconds {
             robots = robots
@@ -88,71 +101,170 @@ impl Bathroom {
                 .map(|r| r.pace(self.x_max, self.y_max))
                 .collect();
         }
-        let ps = robots.iter().map(|r| r.pos).collect::<HashSet<_>>();
+        robots.iter().map(|r| r.pos).collect()
+    }
+
+    /// The robots after `seconds`, one `#` per occupied tile.
+    pub fn display(&self, seconds: usize) -> String {
+        let ps = self.occupied(seconds);
+        let mut out = String::new();
         for y in 0..self.y_max {
             for x in 0..self.x_max {
-                if ps.contains(&(x, y)) {
-                    print!("#");
+                out.push(if ps.contains(&Point::new(y, x)) {
+                    '#'
                 } else {
-                    print!(".")
-                }
+                    '.'
+                });
             }
-            println!()
+            out.push('\n');
         }
+        out
+    }
+}
+
+impl Picture for Bathroom {
+    /// The robots at the second they show the tree.
+    fn picture(&self, scale: usize) -> Image {
+        let map = Grid::new(self.x_max as usize, self.y_max as usize, ());
+        let mut image = Image::from_grid(&map, scale, |_| image::BACKGROUND);
+        image.overlay(self.occupied(self.find_tree()), image::ROBOT);
+        image
+    }
+}
+
+impl Animate for Bathroom {
+    /// The robots moving a second at a time, until they all stand on
+    /// different tiles and show the tree. Each tile shows how many robots are
+    /// on it.
+    fn frames(self, _: Part) -> Frames {
+        let mut robots = Some(self.robots.clone());
+        let mut second = 0;
+        Box::new(std::iter::from_fn(move || {
+            let current = robots.take()?;
+            let mut map = Grid::new(self.x_max as usize, self.y_max as usize, '.');
+            // Robots outside the room have nowhere to be drawn
+            for r in &current {
+                let Some(tile) = map.get_mut(r.pos) else {
+                    continue;
+                };
+                *tile = match *tile {
+                    '.' => '1',
+                    c => c
+                        .to_digit(10)
+                        .and_then(|n| char::from_digit(n + 1, 10))
+                        .unwrap_or('+'),
+                };
+            }
+            let distinct = current.iter().map(|r| r.pos).collect::<HashSet<_>>().len();
+            let status = format!(
+                "second {}, {} of {} robots on their own tile",
+                second,
+                distinct,
+                current.len()
+            );
+            if distinct < current.len() {
+                robots = Some(
+                    current
+                        .iter()
+                        .map(|r| r.pace(self.x_max, self.y_max))
+                        .collect(),
+                );
+                second += 1;
+            }
+            Some(Frame { map, status })
+        }))
     }
 }
 
 #[derive(Clone, Copy)]
 struct Robot {
-    pos: (isize, isize),
-    vel: (isize, isize),
+    pos: Point,
+    vel: Point,
 }
 
 impl Robot {
-    fn new(input: &str) -> Self {
-        let ns = RE
-            .find_iter(input)
-            .map(|n| n.as_str().parse().unwrap())
-            .collect_vec();
-        let pos = (ns[0], ns[1]);
-        let vel = (ns[2], ns[3]);
-        Self { pos, vel }
+    /// Parses a robot, which has to start inside an `x_max` by `y_max` room.
+    fn new(input: &str, x_max: isize, y_max: isize) -> Result<Self, ParseError> {
+        let mut ns = RE.find_iter(input).map(|n| n.as_str());
+        let mut n = || next(input, &mut ns, "number");
+        let inside = |token, max: isize| {
+            number(input, token).and_then(|n: isize| {
+                (0..max).contains(&n).then_some(n).ok_or_else(|| {
+                    ParseError::new(input, token, format!("a position from 0 to {}", max - 1))
+                })
+            })
+        };
+        let pos = Point {
+            x: inside(n()?, x_max)?,
+            y: inside(n()?, y_max)?,
+        };
+        let vel = Point {
+            x: number(input, n()?)?,
+            y: number(input, n()?)?,
+        };
+        Ok(Self { pos, vel })
     }
 
     fn pace(&self, x_max: isize, y_max: isize) -> Self {
-        let wrap = |cur: isize, vel: isize, max: isize| -> isize {
-            let mut r = cur + vel;
-            if r < 0 {
-                r += max;
-            }
-            if r >= max {
-                r -= max;
-            }
-            r
-        };
-        let pos = (
-            wrap(self.pos.0, self.vel.0, x_max),
-            wrap(self.pos.1, self.vel.1, y_max),
+        let pos = Point::new(
+            (self.pos.y + self.vel.y).rem_euclid(y_max),
+            (self.pos.x + self.vel.x).rem_euclid(x_max),
         );
         Self { pos, vel: self.vel }
     }
 }
 
-pub fn run() {
-    let bathroom = Bathroom::new(&read_to_string("inputs/day14.txt").unwrap(), 101, 103);
-    println!("Safety factor: {}", bathroom.safety_factor());
-    let seconds = bathroom.find_tree();
-    println!("Tree time: {}", seconds);
-    bathroom.display(seconds);
+impl Solution for Bathroom {
+    const DAY: usize = 14;
+
+    fn parse(input: &str) -> Result<Self> {
+        Self::parse_with(input, &Params::new())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self> {
+        let [width, height] = read_params(Self::DAY, params, [("width", 101), ("height", 103)])?;
+        Ok(Bathroom::new(input, width as isize, height as isize)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.safety_factor().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.find_tree().into()
+    }
+
+    fn artifacts(&self) -> Vec<(&'static str, String)> {
+        vec![("tree", self.display(self.find_tree()))]
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use super::*;
 
     #[test]
     fn safety_factor() {
-        let bathroom = Bathroom::new(&read_to_string("inputs/day14_small.txt").unwrap(), 11, 7);
+        let bathroom = Bathroom::new(&get_variant(14, "small").unwrap(), 11, 7).unwrap();
         assert_eq!(12, bathroom.safety_factor())
     }
+
+    #[test]
+    fn invalid() {
+        let e = Bathroom::new("p=0,0 v=1,1\np=200,5 v=1,1\n", 101, 103)
+            .err()
+            .unwrap();
+        assert_eq!((2, 3), (e.line, e.column));
+        assert_eq!("a position from 0 to 100", e.expected);
+    }
+
+    #[test]
+    fn fast_robots() {
+        // Fast enough to wrap around the room more than once a second
+        let bathroom = Bathroom::new("p=1,1 v=-25,30\n", 11, 7).unwrap();
+        let robot = bathroom.robots[0].pace(11, 7);
+        assert_eq!(Point::new(3, 9), robot.pos);
+    }
 }
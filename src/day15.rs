@@ -1,133 +1,180 @@
-#![allow(dead_code)]
-use std::{collections::HashMap, fs::read_to_string};
-
-use itertools::Itertools;
+use crate::{
+    animate::{Animate, Frame, Frames},
+    error::Result,
+    geometry::{Direction, Point},
+    parse::ParseError,
+    solution::{Answer, Part, Solution},
+    utils::Grid,
+};
 
 #[derive(Clone)]
-struct Warehouse {
-    grid: HashMap<(isize, isize), Square>,
-    robot: (isize, isize),
+pub struct Warehouse {
+    grid: Grid<Square>,
+    robot: Point,
     moves: Vec<Direction>,
 }
 
 impl Warehouse {
-    fn new(input: &str) -> Self {
-        let mut data = input.split("\n\n");
-        let mut grid = HashMap::new();
-        let mut robot = (0, 0);
-        for (y, line) in data.next().unwrap().lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let pos = (y as isize, x as isize);
-                if c == '#' {
-                    grid.insert(pos, Square::Wall);
-                } else if c == 'O' {
-                    grid.insert(pos, Square::Box);
-                } else if c == '@' {
-                    robot = pos;
-                }
-            }
-        }
-        let moves = data
-            .next()
-            .unwrap()
-            .replace("\n", "")
-            .chars()
-            .map(|c| match c {
-                '^' => Direction::Up,
-                'v' => Direction::Down,
-                '<' => Direction::Left,
-                '>' => Direction::Right,
-                _ => unreachable!(),
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let (map, moves) = input
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::eof(input, "blank line before the moves"))?;
+        let chars = Grid::parse(map, "`#`, `O`, `@` or `.`", |c| {
+            matches!(c, '#' | 'O' | '@' | '.').then_some(c)
+        })?;
+        let robot = chars
+            .find(&'@')
+            .ok_or_else(|| ParseError::eof(map, "robot `@`"))?;
+        let grid = chars.map(|c| match c {
+            '#' => Square::Wall,
+            'O' => Square::Box,
+            _ => Square::Empty,
+        });
+        let moves = moves
+            .char_indices()
+            .filter(|(_, c)| *c != '\n')
+            .map(|(i, c)| {
+                // Only arrows, not the compass letters `from_char` also takes
+                Direction::from_char(c)
+                    .filter(|direction| direction.arrow() == c)
+                    .ok_or_else(|| {
+                        let token = &moves[i..i + c.len_utf8()];
+                        ParseError::new(input, token, "move `^`, `v`, `<` or `>`")
+                    })
             })
-            .collect_vec();
-        Self { grid, robot, moves }
+            .collect::<Result<_, _>>()?;
+        Ok(Self { grid, robot, moves })
     }
 
-    fn simulate(&mut self) {
+    pub fn simulate(&mut self) {
         for direction in self.moves.clone().iter() {
             self.shift(*direction);
         }
     }
 
     fn shift(&mut self, direction: Direction) {
-        let target = direction.target(self.robot);
+        let target = self.robot + direction;
         if self.shift_inner(target, direction, false, false) {
             let _ = self.shift_inner(target, direction, false, true);
             self.robot = target;
         }
     }
 
-    fn shift_inner(
-        &mut self,
-        pos: (isize, isize),
-        direction: Direction,
-        inner: bool,
-        shift: bool,
-    ) -> bool {
-        let square = self.grid.get(&pos);
+    fn shift_inner(&mut self, pos: Point, direction: Direction, inner: bool, shift: bool) -> bool {
+        // Nothing moves off the edge of the map, walled in or not
+        let square = self.grid.get(pos).copied();
         match square {
-            None => true,
-            Some(&Square::Wall) => false,
-            Some(&Square::Box) => {
-                let target = direction.target(pos);
-                let foo = self.shift_inner(target, direction, false, shift);
-                if foo && shift {
-                    self.grid.remove(&pos);
-                    self.grid.insert(target, Square::Box);
+            Some(Square::Empty) => true,
+            None | Some(Square::Wall) => false,
+            Some(Square::Box) => {
+                let target = pos + direction;
+                let shifted = self.shift_inner(target, direction, false, shift);
+                if shifted && shift {
+                    self.grid[pos] = Square::Empty;
+                    self.grid[target] = Square::Box;
                 }
-                foo
+                shifted
             }
-            Some(&Square::LeftBox) | Some(&Square::RightBox) => {
-                let square = *square.unwrap();
-                let target = direction.target(pos);
-                let foo;
+            Some(square @ (Square::LeftBox | Square::RightBox)) => {
+                let target = pos + direction;
+                let shifted;
                 if inner {
-                    foo = self.shift_inner(target, direction, false, shift);
+                    shifted = self.shift_inner(target, direction, false, shift);
                 } else {
                     let opposite = square.opposite();
                     if direction == opposite {
-                        foo = self.shift_inner(target, direction, true, shift)
+                        shifted = self.shift_inner(target, direction, true, shift)
                     } else {
-                        foo = self.shift_inner(target, direction, false, shift)
-                            && self.shift_inner(opposite.target(pos), direction, true, shift)
+                        shifted = self.shift_inner(target, direction, false, shift)
+                            && self.shift_inner(pos + opposite, direction, true, shift)
                     }
                 }
-                if foo && shift {
-                    self.grid.remove(&pos);
-                    self.grid.insert(target, square);
+                if shifted && shift {
+                    self.grid[pos] = Square::Empty;
+                    self.grid[target] = square;
                 }
-                foo
+                shifted
             }
         }
     }
 
-    fn gps_sum(&self) -> isize {
+    pub fn gps_sum(&self) -> isize {
         self.grid
             .iter()
             .filter(|(_, square)| **square == Square::Box || **square == Square::LeftBox)
-            .map(|(pos, _)| pos.0 * 100 + pos.1)
+            .map(|(pos, _)| pos.y * 100 + pos.x)
             .sum()
     }
 
-    fn widen(&mut self) {
-        let mut new = HashMap::new();
+    pub fn widen(&mut self) {
+        let mut new = Grid::new(self.grid.width() * 2, self.grid.height(), Square::Empty);
         for (pos, space) in self.grid.iter() {
             let (fst, snd) = match space {
                 Square::Wall => (Square::Wall, Square::Wall),
                 Square::Box => (Square::LeftBox, Square::RightBox),
+                Square::Empty => (Square::Empty, Square::Empty),
                 _ => unreachable!(),
             };
-            let pos = (pos.0, pos.1 * 2);
-            new.insert(pos, fst);
-            new.insert((pos.0, pos.1 + 1), snd);
+            let pos = Point::new(pos.y, pos.x * 2);
+            new[pos] = fst;
+            new[pos + Direction::Right] = snd;
         }
         self.grid = new;
-        self.robot = (self.robot.0, self.robot.1 * 2)
+        self.robot = Point::new(self.robot.y, self.robot.x * 2)
+    }
+}
+
+impl Warehouse {
+    fn draw(&self) -> Grid<char> {
+        let mut map = self.grid.map(|square| match square {
+            Square::Empty => '.',
+            Square::Wall => '#',
+            Square::Box => 'O',
+            Square::LeftBox => '[',
+            Square::RightBox => ']',
+        });
+        map[self.robot] = '@';
+        map
+    }
+}
+
+impl Animate for Warehouse {
+    /// The robot's moves, on the widened warehouse for part 2.
+    fn frames(mut self, part: Part) -> Frames {
+        if part == Part::Two {
+            self.widen();
+        }
+        let moves = std::mem::take(&mut self.moves);
+        let total = moves.len();
+        let mut moves = moves.into_iter().enumerate();
+        let mut started = false;
+        Box::new(std::iter::from_fn(move || {
+            let moved = if started {
+                let (i, direction) = moves.next()?;
+                self.shift(direction);
+                format!("move {}/{} {}", i + 1, total, direction.arrow())
+            } else {
+                started = true;
+                format!("move 0/{}", total)
+            };
+            let status = format!(
+                "{}, robot at {},{}, GPS sum {}",
+                moved,
+                self.robot.x,
+                self.robot.y,
+                self.gps_sum()
+            );
+            Some(Frame {
+                map: self.draw(),
+                status,
+            })
+        }))
     }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 enum Square {
+    Empty,
     Wall,
     Box,
     LeftBox,
@@ -144,55 +191,65 @@ impl Square {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+impl Solution for Warehouse {
+    const DAY: usize = 15;
+    // Boxes stop at the edge of maps without walls
+    const VERSION: u32 = 2;
 
-impl Direction {
-    fn target(&self, pos: (isize, isize)) -> (isize, isize) {
-        let (y, x) = pos;
-        match self {
-            Self::Up => (y - 1, x),
-            Self::Down => (y + 1, x),
-            Self::Left => (y, x - 1),
-            Self::Right => (y, x + 1),
-        }
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Warehouse::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        let mut warehouse = self.clone();
+        warehouse.simulate();
+        warehouse.gps_sum().into()
     }
-}
 
-pub fn run() {
-    let input = read_to_string("inputs/day15.txt").unwrap();
-    let mut warehouse = Warehouse::new(&input);
-    let mut wide = warehouse.clone();
-    wide.widen();
-    wide.simulate();
-    warehouse.simulate();
-    println!("GPS sum: {}", warehouse.gps_sum());
-    println!("Wide GPS sum: {}", wide.gps_sum());
+    fn part2(&self) -> Answer {
+        let mut wide = self.clone();
+        wide.widen();
+        wide.simulate();
+        wide.gps_sum().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs::read_to_string;
+    use crate::input::get_variant;
 
     use super::Warehouse;
 
     #[test]
     fn gps_sum() {
-        let mut warehouse = Warehouse::new(&read_to_string("inputs/day15_small.txt").unwrap());
+        let mut warehouse = Warehouse::new(&get_variant(15, "small").unwrap()).unwrap();
         warehouse.simulate();
         assert_eq!(10092, warehouse.gps_sum())
     }
 
     #[test]
     fn wide_gps_sum() {
-        let mut warehouse = Warehouse::new(&read_to_string("inputs/day15_small.txt").unwrap());
+        let mut warehouse = Warehouse::new(&get_variant(15, "small").unwrap()).unwrap();
         warehouse.widen();
         warehouse.simulate();
         assert_eq!(9021, warehouse.gps_sum())
     }
+
+    #[test]
+    fn no_walls() {
+        let mut warehouse = Warehouse::new("@O\n\n>>\n").unwrap();
+        warehouse.simulate();
+        assert_eq!(1, warehouse.gps_sum());
+        let mut warehouse = Warehouse::new("@O\n\n>>\n").unwrap();
+        warehouse.widen();
+        warehouse.simulate();
+        assert_eq!(2, warehouse.gps_sum());
+    }
+
+    #[test]
+    fn unknown_move() {
+        let err = Warehouse::new("#@.#\n\n<>x").err().unwrap();
+        assert_eq!((3, 3), (err.line, err.column));
+        assert_eq!("move `^`, `v`, `<` or `>`", err.expected);
+    }
 }
@@ -1,220 +1,199 @@
-#![allow(dead_code)]
-use std::{
-    cmp::Reverse,
-    collections::{BinaryHeap, HashMap, HashSet},
-    fs::read_to_string,
+use std::collections::HashSet;
+
+use crate::{
+    animate::{Animate, Frame, Frames},
+    error::Result,
+    geometry::{Direction, Point},
+    graph::{self, Paths},
+    image::{self, Image, Picture},
+    parse::ParseError,
+    solution::{Answer, Part, Solution},
+    utils::Grid,
 };
 
-struct ReindeerMaze {
+pub struct ReindeerMaze {
     start: Point,
     end: Point,
-    edges: HashMap<Node, Vec<(Node, u32)>>,
+    open: Grid<bool>,
 }
 
 impl ReindeerMaze {
-    fn new(input: &str) -> Self {
-        let mut start = (0, 0);
-        let mut end = (0, 0);
-
-        let mut points = HashSet::new();
-        for (y, line) in input.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let p = (y as isize, x as isize);
-                if c == 'S' {
-                    start = p;
-                } else if c == 'E' {
-                    end = p;
-                }
-                if c != '#' {
-                    points.insert(p);
-                }
-            }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input, "`#`, `.`, `S` or `E`", |c| {
+            matches!(c, '#' | '.' | 'S' | 'E').then_some(c)
+        })?;
+        let start = grid
+            .find(&'S')
+            .ok_or_else(|| ParseError::eof(input, "start `S`"))?;
+        let end = grid
+            .find(&'E')
+            .ok_or_else(|| ParseError::eof(input, "end `E`"))?;
+        let open = grid.map(|c| *c != '#');
+        let neighbours = |p: &Point| {
+            open.neighbours(*p)
+                .filter(|n| open[*n])
+                .map(|n| (n, 1))
+                .collect::<Vec<_>>()
+        };
+        if graph::bfs(&neighbours, [start]).distance(&end).is_none() {
+            let at = input.find('E').expect("the end was found");
+            return Err(ParseError::new(
+                input,
+                &input[at..at + 1],
+                "an end `E` reachable from the start",
+            ));
         }
-
-        let mut edges = HashMap::<_, Vec<_>>::new();
-        for point in points.iter() {
-            for d in Direction::all() {
-                let node = (*point, d);
-                let adj = point.adjacent(d);
-                if points.contains(&adj) {
-                    let new_node = (adj, d);
-                    edges.entry(node).or_default().push((new_node, 1));
-                }
-                for d2 in [d.counterclockwise(), d.clockwise()] {
-                    let new_node = (*point, d2);
-                    edges.entry(node).or_default().push((new_node, 1000));
-                }
-            }
-        }
-        Self { start, end, edges }
+        Ok(Self { start, end, open })
     }
 
-    fn dijkstra(&self) -> (HashMap<Node, Vec<Node>>, HashMap<Node, u32>, u32) {
-        let mut previous_nodes = HashMap::<_, Vec<_>>::new();
-        let mut distances = HashMap::new();
-        let mut unvisited = self.edges.keys().collect::<HashSet<&Node>>();
-        let mut q = BinaryHeap::new();
-        let start = (self.start, Direction::Right);
-        q.push((Reverse(0), start));
-
-        while !unvisited.is_empty() {
-            let (Reverse(src_score), src_node) = q.pop().unwrap();
-            if !unvisited.contains(&src_node) {
-                continue;
-            }
-            for (dst_node, dst_score) in self.edges.get(&src_node).unwrap() {
-                let new_score = src_score + dst_score;
-                q.push((Reverse(new_score), *dst_node));
-                if new_score == *distances.entry(*dst_node).or_insert(u32::MAX) {
-                    previous_nodes.entry(*dst_node).or_default().push(src_node);
-                }
-                if new_score < distances[dst_node] {
-                    distances.insert(*dst_node, new_score);
-                    previous_nodes.insert(*dst_node, [src_node].into());
-                }
-            }
-            unvisited.remove(&src_node);
-        }
-
-        let lowest_score = *distances
-            .iter()
-            .filter(|x| x.0 .0 == self.end)
-            .map(|x| x.1)
-            .min()
-            .unwrap();
-        (previous_nodes, distances, lowest_score)
-    }
-
-    fn count_tiles(
-        &self,
-        previous_nodes: &HashMap<Node, Vec<Node>>,
-        distances: &HashMap<Node, u32>,
-        lowest_score: u32,
-    ) -> usize {
-        distances
-            .iter()
-            .filter(|x| x.0 .0 == self.end && *x.1 == lowest_score)
-            .flat_map(|x| {
-                let mut visited = Vec::new();
-                ReindeerMaze::count_tiles_helper(*x.0, previous_nodes, &mut visited);
-                visited
-            })
-            .map(|x| x.0)
-            .collect::<HashSet<_>>()
-            .len()
-    }
-
-    fn count_tiles_helper(
-        cur: Node,
-        previous_nodes: &HashMap<Node, Vec<Node>>,
-        visited: &mut Vec<Node>,
-    ) {
-        visited.push(cur);
-        if let Some(nodes) = previous_nodes.get(&cur) {
-            for node in nodes {
-                if !visited.contains(&node) {
-                    ReindeerMaze::count_tiles_helper(*node, previous_nodes, visited);
-                }
-            }
-        }
+    pub fn lowest_score(&self) -> u32 {
+        let paths = self.dijkstra();
+        self.lowest_end_score(&paths)
     }
-}
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
+    /// Number of tiles on any of the best paths through the maze.
+    pub fn best_path_tiles(&self) -> usize {
+        self.best_tiles().len()
+    }
 
-impl Direction {
-    fn all() -> [Self; 4] {
-        [Self::Up, Self::Down, Self::Left, Self::Right]
+    fn best_tiles(&self) -> HashSet<Point> {
+        let paths = self.dijkstra();
+        let lowest_score = self.lowest_end_score(&paths);
+        let ends = Direction::ALL
+            .into_iter()
+            .map(|d| (self.end, d))
+            .filter(|node| paths.distance(node) == Some(lowest_score));
+        paths
+            .on_shortest_paths(ends)
+            .into_iter()
+            .map(|(point, _)| point)
+            .collect()
     }
 
-    fn clockwise(&self) -> Self {
-        match self {
-            Self::Up => Self::Right,
-            Self::Right => Self::Down,
-            Self::Down => Self::Left,
-            Self::Left => Self::Up,
-        }
+    /// Moving forward costs 1 and turning on the spot costs 1000.
+    fn dijkstra(&self) -> Paths<Node> {
+        let neighbours = |&(point, d): &Node| {
+            let forward = point + d;
+            self.open
+                .get(forward)
+                .is_some_and(|open| *open)
+                .then_some(((forward, d), 1))
+                .into_iter()
+                .chain([
+                    ((point, d.counterclockwise()), 1000),
+                    ((point, d.clockwise()), 1000),
+                ])
+        };
+        graph::dijkstra(&neighbours, [(self.start, Direction::Right)])
     }
 
-    fn counterclockwise(&self) -> Self {
-        match self {
-            Self::Up => Self::Left,
-            Self::Left => Self::Down,
-            Self::Down => Self::Right,
-            Self::Right => Self::Up,
-        }
+    fn lowest_end_score(&self, paths: &Paths<Node>) -> u32 {
+        Direction::ALL
+            .into_iter()
+            .filter_map(|d| paths.distance(&(self.end, d)))
+            .min()
+            .unwrap()
     }
 }
 
-type Point = (isize, isize);
+type Node = (Point, Direction);
 
-trait PointStuff {
-    fn adjacent(&self, d: Direction) -> Self;
+impl Picture for ReindeerMaze {
+    /// The maze with every tile on a best path highlighted.
+    fn picture(&self, scale: usize) -> Image {
+        let mut image = Image::from_grid(&self.open, scale, |open| {
+            if *open {
+                image::FLOOR
+            } else {
+                image::WALL
+            }
+        });
+        image.overlay(self.best_tiles(), image::PATH);
+        image.paint(self.start, image::START);
+        image.paint(self.end, image::END);
+        image
+    }
 }
 
-impl PointStuff for Point {
-    fn adjacent(&self, d: Direction) -> Self {
-        let (y, x) = *self;
-        match d {
-            Direction::Up => (y - 1, x),
-            Direction::Down => (y + 1, x),
-            Direction::Left => (y, x - 1),
-            Direction::Right => (y, x + 1),
-        }
+impl Animate for ReindeerMaze {
+    /// The reindeer following one of the best paths to the end.
+    fn frames(self, _: Part) -> Frames {
+        let paths = self.dijkstra();
+        let score = self.lowest_end_score(&paths);
+        let end = Direction::ALL
+            .into_iter()
+            .map(|d| (self.end, d))
+            .find(|node| paths.distance(node) == Some(score))
+            .unwrap();
+        let path = paths.path(&end).unwrap();
+        let mut map = self.open.map(|open| if *open { '.' } else { '#' });
+        map[self.start] = 'S';
+        map[self.end] = 'E';
+        Box::new(path.into_iter().map(move |(point, d)| {
+            let mut frame = map.clone();
+            frame[point] = d.arrow();
+            map[point] = '+';
+            let status = format!(
+                "score {}, reindeer at {},{} facing {:?}",
+                paths.distance(&(point, d)).unwrap(),
+                point.x,
+                point.y,
+                d
+            );
+            Frame { map: frame, status }
+        }))
     }
 }
 
-type Node = (Point, Direction);
+impl Solution for ReindeerMaze {
+    const DAY: usize = 16;
 
-pub fn run() {
-    let rm = ReindeerMaze::new(&read_to_string("inputs/day16.txt").unwrap());
-    let (previous_nodes, distances, lowest_score) = rm.dijkstra();
-    println!("Lowest score: {}", lowest_score);
-    let tile_count = rm.count_tiles(&previous_nodes, &distances, lowest_score);
-    println!("Tile count: {}", tile_count);
+    fn parse(input: &str) -> Result<Self> {
+        Ok(ReindeerMaze::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.lowest_score().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.best_path_tiles().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use super::*;
 
     #[test]
     fn lowest_score() {
-        let rm = ReindeerMaze::new(&read_to_string("inputs/day16_small.txt").unwrap());
-        let (_, _, lowest_score) = rm.dijkstra();
-        assert_eq!(7036, lowest_score)
+        let rm = ReindeerMaze::new(&get_variant(16, "small").unwrap()).unwrap();
+        assert_eq!(7036, rm.lowest_score())
     }
 
     #[test]
     fn lowest_score2() {
-        let rm = ReindeerMaze::new(&read_to_string("inputs/day16_small2.txt").unwrap());
-        let (_, _, lowest_score) = rm.dijkstra();
-        assert_eq!(11048, lowest_score)
+        let rm = ReindeerMaze::new(&get_variant(16, "small2").unwrap()).unwrap();
+        assert_eq!(11048, rm.lowest_score())
     }
 
     #[test]
     fn count_tiles() {
-        let rm = ReindeerMaze::new(&read_to_string("inputs/day16_small.txt").unwrap());
-        let (previous_nodes, distances, lowest_score) = rm.dijkstra();
-        assert_eq!(
-            45,
-            rm.count_tiles(&previous_nodes, &distances, lowest_score)
-        )
+        let rm = ReindeerMaze::new(&get_variant(16, "small").unwrap()).unwrap();
+        assert_eq!(45, rm.best_path_tiles())
     }
 
     #[test]
     fn count_tiles2() {
-        let rm = ReindeerMaze::new(&read_to_string("inputs/day16_small2.txt").unwrap());
-        let (previous_nodes, distances, lowest_score) = rm.dijkstra();
-        assert_eq!(
-            64,
-            rm.count_tiles(&previous_nodes, &distances, lowest_score)
-        )
+        let rm = ReindeerMaze::new(&get_variant(16, "small2").unwrap()).unwrap();
+        assert_eq!(64, rm.best_path_tiles())
+    }
+    #[test]
+    fn unreachable_end() {
+        let e = ReindeerMaze::new("#####\n#S#E#\n#####\n").err().unwrap();
+        assert_eq!((2, 4), (e.line, e.column));
+        assert_eq!("an end `E` reachable from the start", e.expected);
     }
 }
@@ -1,10 +1,14 @@
-#![allow(dead_code)]
-use core::panic;
-use std::fs::read_to_string;
-
 use itertools::Itertools;
 
-struct Cpu {
+use crate::{
+    cancel,
+    error::Result,
+    parse::{lines, next, number, ParseError},
+    solution::{Answer, Solution},
+};
+
+#[derive(Clone)]
+pub struct Cpu {
     ra: u64,
     rb: u64,
     rc: u64,
@@ -14,55 +18,109 @@ struct Cpu {
 }
 
 impl Cpu {
-    fn new(input: &str) -> Self {
-        let mut data = input.split("\n\n");
-        let (ra, rb, rc) = data
-            .next()
-            .unwrap()
-            .lines()
-            .map(|line| line.split(": ").skip(1).next().unwrap().parse().unwrap())
-            .collect_tuple()
-            .unwrap();
-        let program = data
-            .next()
-            .unwrap()
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let (registers, program) = input
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::eof(input, "blank line before the program"))?;
+        let mut values = lines(registers, |line| {
+            let mut data = line.split(": ").skip(1);
+            number(line, next(line, &mut data, "`: `")?)
+        })?
+        .into_iter();
+        let mut register = |expected| {
+            values
+                .next()
+                .ok_or_else(|| ParseError::eof(registers, expected))
+        };
+        let (ra, rb, rc) = (
+            register("register A")?,
+            register("register B")?,
+            register("register C")?,
+        );
+        let ops = program
             .trim_end()
             .split(": ")
-            .skip(1)
-            .next()
-            .unwrap()
-            .split(",")
-            .map(|n| n.parse().unwrap())
-            .collect_vec();
-        Self {
+            .nth(1)
+            .ok_or_else(|| ParseError::eof(input, "`: `"))?;
+        let tokens = ops.split(",").collect_vec();
+        let program = tokens
+            .iter()
+            .map(|n| match number(input, n) {
+                Ok(op) if op < 8 => Ok(op),
+                _ => Err(ParseError::new(input, n, "3-bit number")),
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        if program.len() % 2 == 1 {
+            return Err(ParseError::eof(input, "operand"));
+        }
+        // Combo operand 7 is reserved, so programs using it are invalid
+        for (i, op) in program.chunks_exact(2).enumerate() {
+            if matches!(op[0], 0 | 2 | 5 | 6 | 7) && op[1] == 7 {
+                return Err(ParseError::new(
+                    input,
+                    tokens[2 * i + 1],
+                    "combo operand 0-6",
+                ));
+            }
+        }
+        Ok(Self {
             ra,
             rb,
             rc,
             ip: 0,
             program,
             output: Vec::new(),
-        }
+        })
     }
 
-    fn run(&mut self) {
+    pub fn run(&mut self) {
         while let Some(combo) = self.program.get(self.ip + 1) {
             let opcode = self.program.get(self.ip).unwrap();
             Instruction::new(*opcode, *combo).execute(self);
         }
     }
 
-    fn dump_instructions(&mut self) {
-        while let Some(combo) = self.program.get(self.ip + 1) {
-            let opcode = self.program.get(self.ip).unwrap();
-            let inst = Instruction::new(*opcode, *combo);
-            println!("{}", inst.decompile());
-            inst.execute(self);
-        }
+    /// Registers A, B and C.
+    pub fn registers(&self) -> (u64, u64, u64) {
+        (self.ra, self.rb, self.rc)
+    }
+
+    /// The program as one line of pseudocode per instruction, without
+    /// running it.
+    pub fn disassemble(&self) -> Vec<String> {
+        self.program
+            .chunks_exact(2)
+            .map(|op| Instruction::new(op[0], op[1]).decompile())
+            .collect()
     }
 
-    fn output(&self) -> String {
+    pub fn output(&self) -> String {
         self.output.iter().join(",")
     }
+
+    pub fn run_with_ra(&self, ra: u64) -> Vec<u8> {
+        let mut cpu = self.clone();
+        cpu.ra = ra;
+        cpu.run();
+        cpu.output
+    }
+
+    /// The lowest value of register A that makes the program output itself,
+    /// if there is one.
+    pub fn find_recursive_ra(&self) -> Option<u64> {
+        let mut ras = Vec::from([0_u64]);
+        let program = &self.program;
+
+        for i in 0..program.len() {
+            ras = ras
+                .into_iter()
+                .flat_map(|ra| (0..8).map(move |end| (ra << 3) + end))
+                .filter(|ra| self.run_with_ra(*ra) == program[program.len() - 1 - i..])
+                .collect_vec();
+        }
+
+        ras.into_iter().min()
+    }
 }
 
 struct Instruction {
@@ -101,6 +159,8 @@ impl Instruction {
             InstructionKind::Bst => cpu.rb = combo % 8,
             InstructionKind::Jnz => {
                 if cpu.ra != 0 {
+                    // Programs that never clear A loop forever
+                    cancel::checkpoint();
                     jumped = true;
                     cpu.ip = self.operand as usize;
                 }
@@ -116,7 +176,8 @@ impl Instruction {
     }
 
     fn decompile(&self) -> String {
-        let combo = match self.operand {
+        // Only some instructions take a combo operand
+        let combo = || match self.operand {
             0..=3 => format!("{}", self.operand),
             4 => "rA".to_string(),
             5 => "rB".to_string(),
@@ -124,14 +185,14 @@ impl Instruction {
             _ => panic!("Invalid combo"),
         };
         match self.kind {
-            InstructionKind::Adv => format!("rA /= 2**{}", combo),
+            InstructionKind::Adv => format!("rA /= 2**{}", combo()),
             InstructionKind::Bxl => format!("rB ^= {}", self.operand),
-            InstructionKind::Bst => format!("rB = {} % 8", combo),
+            InstructionKind::Bst => format!("rB = {} % 8", combo()),
             InstructionKind::Jnz => format!("ip = {} if rA != 0", self.operand),
-            InstructionKind::Bxc => format!("rB ^= rC"),
-            InstructionKind::Out => format!("push {} % 8", combo),
-            InstructionKind::Bdv => format!("rB = rA / 2**{}", combo),
-            InstructionKind::Cdv => format!("rC = rA / 2**{}", combo),
+            InstructionKind::Bxc => "rB ^= rC".to_string(),
+            InstructionKind::Out => format!("push {} % 8", combo()),
+            InstructionKind::Bdv => format!("rB = rA / 2**{}", combo()),
+            InstructionKind::Cdv => format!("rC = rA / 2**{}", combo()),
         }
     }
 }
@@ -147,48 +208,67 @@ enum InstructionKind {
     Cdv,
 }
 
-fn run_with_ra(ra: u64, input: &str) -> Vec<u8> {
-    let mut cpu = Cpu::new(input);
-    cpu.ra = ra;
-    cpu.run();
-    cpu.output
-}
+impl Solution for Cpu {
+    const DAY: usize = 17;
 
-fn find_recursive_ra(input: &str) -> u64 {
-    let mut ras = Vec::from([0_u64]);
-    let mut cpu = Cpu::new(input);
-    cpu.run();
-    let program = cpu.program;
-
-    for i in 0..program.len() {
-        ras = ras
-            .into_iter()
-            .flat_map(|ra| (0..8).map(move |end| (ra << 3) + end))
-            .filter(|ra| run_with_ra(*ra, input) == program[program.len() - 1 - i..])
-            .collect_vec();
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Cpu::new(input)?)
     }
 
-    *ras.iter().min().unwrap()
-}
+    fn part1(&self) -> Answer {
+        let mut cpu = self.clone();
+        cpu.run();
+        cpu.output().into()
+    }
 
-pub fn run() {
-    let input = read_to_string("inputs/day17.txt").unwrap();
-    let mut cpu = Cpu::new(&input);
-    cpu.run();
-    println!("Output: {}", cpu.output());
-    // let mut cpu = Cpu::new(&input);
-    // cpu.dump_instructions();
-    println!("Register A: {}", find_recursive_ra(&input));
+    fn part2(&self) -> Answer {
+        self.find_recursive_ra().map_or(Answer::None, Answer::from)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use super::*;
 
     #[test]
     fn output() {
-        let mut cpu = Cpu::new(&read_to_string("inputs/day17_small.txt").unwrap());
+        let mut cpu = Cpu::new(&get_variant(17, "small").unwrap()).unwrap();
         cpu.run();
         assert_eq!("4,6,3,5,6,3,5,2,1,0", cpu.output())
     }
+
+    #[test]
+    fn invalid_opcode() {
+        let input = "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,8";
+        let err = Cpu::new(input).err().unwrap();
+        assert_eq!((5, 12), (err.line, err.column));
+        assert_eq!("3-bit number", err.expected);
+    }
+
+    #[test]
+    fn invalid_program() {
+        let registers = "Register A: 1\nRegister B: 0\nRegister C: 0\n\n";
+        let err = Cpu::new(&format!("{}Program: 1,7,5,7\n", registers))
+            .err()
+            .unwrap();
+        assert_eq!((5, 16), (err.line, err.column));
+        assert_eq!("combo operand 0-6", err.expected);
+        let err = Cpu::new(&format!("{}Program: 0,1,5", registers))
+            .err()
+            .unwrap();
+        assert_eq!("operand", err.expected);
+        assert_eq!("end of input", err.found);
+    }
+
+    #[test]
+    fn quine() {
+        let registers = "Register A: 2024\nRegister B: 0\nRegister C: 0\n\n";
+        let cpu = Cpu::new(&format!("{}Program: 0,3,5,4,3,0\n", registers)).unwrap();
+        assert_eq!(Some(117440), cpu.find_recursive_ra());
+        // Only ever outputs one number, so can't output itself
+        let cpu = Cpu::new(&format!("{}Program: 5,4\n", registers)).unwrap();
+        assert_eq!(None, cpu.find_recursive_ra());
+    }
 }
@@ -1,12 +1,15 @@
-#![allow(dead_code)]
-use std::{
-    collections::{HashMap, HashSet},
-    fs::read_to_string,
-};
+use tracing::instrument;
 
-use itertools::Itertools;
+use crate::{
+    error::Result,
+    geometry::Point,
+    graph,
+    parse::{lines, next, number, ParseError},
+    solution::{read_params, Answer, Params, Solution},
+    utils::Grid,
+};
 
-struct Computer {
+pub struct Computer {
     bytes: Vec<Point>,
     width: isize,
     start: Point,
@@ -15,103 +18,108 @@ struct Computer {
 }
 
 impl Computer {
-    fn new(input: &str, width: isize, count: usize) -> Self {
-        let bytes = input
-            .lines()
-            .map(|line| {
-                let (x, y) = line
-                    .split(",")
-                    .map(|n| n.parse().unwrap())
-                    .collect_tuple()
-                    .unwrap();
-                (y, x)
-            })
-            .collect_vec();
-        Self {
+    pub fn new(input: &str, width: isize, count: usize) -> Result<Self, ParseError> {
+        let bytes = lines(input, |line| {
+            let mut data = line.split(",");
+            let x = number(line, next(line, &mut data, "number")?)?;
+            let y = number(line, next(line, &mut data, "`,`")?)?;
+            Ok(Point::new(y, x))
+        })?;
+        if bytes.len() < count {
+            return Err(ParseError::eof(input, format!("at least {} bytes", count)));
+        }
+        Ok(Self {
             bytes,
             width,
-            start: (0, 0),
-            exit: (width - 1, width - 1),
+            start: Point::ORIGIN,
+            exit: Point::new(width - 1, width - 1),
             count,
-        }
+        })
     }
 
-    fn drop_bytes(&self, count: usize) -> HashMap<Point, Space> {
-        let bytes = &self.bytes[0..count]
-            .iter()
-            .copied()
-            .collect::<HashSet<Point>>();
-        (0..self.width)
-            .flat_map(|y| {
-                (0..self.width).map(move |x| {
-                    let p = (y, x);
-                    let space = if bytes.contains(&p) {
-                        Space::Corrupted
-                    } else {
-                        Space::Safe
-                    };
-                    (p, space)
-                })
-            })
-            .collect::<HashMap<_, _>>()
-    }
-
-    fn steps(&self) -> u32 {
-        let grid = self.drop_bytes(self.count);
-        let mut cache = HashMap::new();
-        self.steps_inner(self.start, 0, &mut cache, &grid);
-        *cache.get(&self.exit).unwrap()
-    }
-
-    fn steps_inner(
-        &self,
-        p: Point,
-        steps: u32,
-        cache: &mut HashMap<Point, u32>,
-        grid: &HashMap<Point, Space>,
-    ) {
-        let space = grid.get(&p);
-        if space == None || space == Some(&Space::Corrupted) {
-            return;
-        }
-        if let Some(v) = cache.get(&p) {
-            if v <= &steps {
-                return;
+    #[instrument(skip_all)]
+    fn drop_bytes(&self, count: usize) -> Grid<Space> {
+        let width = self.width as usize;
+        let mut grid = Grid::new(width, width, Space::Safe);
+        for p in self.bytes[0..count].iter() {
+            if let Some(space) = grid.get_mut(*p) {
+                *space = Space::Corrupted;
             }
         }
-        cache.insert(p, steps);
-        let (y, x) = p;
-        for p in [(y - 1, x), (y + 1, x), (y, x - 1), (y, x + 1)] {
-            self.steps_inner(p, steps + 1, cache, grid);
+        grid
+    }
+
+    fn neighbours<'a>(grid: &'a Grid<Space>) -> impl Fn(&Point) -> Vec<(Point, u32)> + 'a {
+        |p| {
+            grid.neighbours(*p)
+                .filter(|p| grid[*p] == Space::Safe)
+                .map(|p| (p, 1))
+                .collect()
         }
     }
 
-    fn reachable(&self, grid: &HashMap<Point, Space>) -> bool {
-        self.reachable_inner(self.start, &mut HashSet::new(), grid)
+    /// Steps to the exit once the first bytes have fallen, if it can still be
+    /// reached.
+    pub fn steps(&self) -> Option<u32> {
+        self.steps_after(self.count)
     }
 
-    fn reachable_inner(
-        &self,
-        p: Point,
-        visited: &mut HashSet<Point>,
-        grid: &HashMap<Point, Space>,
-    ) -> bool {
-        let space = grid.get(&p);
-        if space == None || space == Some(&Space::Corrupted) || visited.contains(&p) {
-            return false;
-        }
-        if p == self.exit {
-            return true;
+    /// Steps to the exit once `count` bytes have fallen, if it can still be
+    /// reached.
+    pub fn steps_after(&self, count: usize) -> Option<u32> {
+        Some(self.shortest_path(count)?.len() as u32 - 1)
+    }
+
+    /// Width of the smallest memory space every byte falls inside.
+    pub fn width_needed(&self) -> isize {
+        self.bytes
+            .iter()
+            .map(|p| p.x.max(p.y) + 1)
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Number of bytes in the input.
+    pub fn byte_count(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// The memory space after `count` bytes have fallen, with `#` for
+    /// corrupted spaces and `O` along a shortest path to the exit.
+    pub fn map(&self, count: usize) -> Grid<char> {
+        let mut map = self.drop_bytes(count).map(|space| match space {
+            Space::Safe => '.',
+            Space::Corrupted => '#',
+        });
+        for p in self.shortest_path(count).into_iter().flatten() {
+            map[p] = 'O';
         }
-        visited.insert(p);
-        let (y, x) = p;
-        [(y - 1, x), (y + 1, x), (y, x - 1), (y, x + 1)]
-            .into_iter()
-            .any(|p| self.reachable_inner(p, visited, grid))
+        map
+    }
+
+    fn shortest_path(&self, count: usize) -> Option<Vec<Point>> {
+        let grid = self.drop_bytes(count);
+        let paths = graph::bfs(&Computer::neighbours(&grid), [self.start]);
+        paths.path(&self.exit)
+    }
+
+    fn reachable(&self, grid: &Grid<Space>) -> bool {
+        let exit = |p: &Point| *p == self.exit;
+        let heuristic = |p: &Point| p.manhattan(self.exit) as u32;
+        graph::astar(&Computer::neighbours(grid), self.start, exit, heuristic).is_some()
     }
 
-    fn first_byte(&self) -> Point {
-        let mut low = self.count;
+    /// The first byte after which the exit can't be reached, if any does
+    /// that.
+    pub fn first_byte(&self) -> Option<Point> {
+        if self.reachable(&self.drop_bytes(self.bytes.len())) {
+            return None;
+        }
+        // The exit is reachable after `low` bytes and cut off after `high`
+        let mut low = match self.reachable(&self.drop_bytes(self.count)) {
+            true => self.count,
+            false => 0,
+        };
         let mut high = self.bytes.len();
         while high - low > 1 {
             let mid = (high + low) / 2;
@@ -121,38 +129,64 @@ impl Computer {
                 high = mid;
             }
         }
-        self.bytes[low]
+        Some(self.bytes[low])
     }
 }
 
-type Point = (isize, isize);
-
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 enum Space {
     Safe,
     Corrupted,
 }
 
-pub fn run() {
-    let computer = Computer::new(&read_to_string("inputs/day18.txt").unwrap(), 71, 1024);
-    println!("Minimum steps: {}", computer.steps());
-    let fb = computer.first_byte();
-    println!("First byte: {},{}", fb.1, fb.0);
+impl Solution for Computer {
+    const DAY: usize = 18;
+
+    fn parse(input: &str) -> Result<Self> {
+        Self::parse_with(input, &Params::new())
+    }
+
+    fn parse_with(input: &str, params: &Params) -> Result<Self> {
+        let [width, bytes] = read_params(Self::DAY, params, [("width", 71), ("bytes", 1024)])?;
+        Ok(Computer::new(input, width as isize, bytes as usize)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.steps().map_or(Answer::None, Answer::from)
+    }
+
+    fn part2(&self) -> Answer {
+        self.first_byte()
+            .map_or(Answer::None, |fb| format!("{},{}", fb.x, fb.y).into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use crate::day18::*;
 
     #[test]
     fn minimum_steps() {
-        let computer = Computer::new(&read_to_string("inputs/day18_small.txt").unwrap(), 7, 12);
-        assert_eq!(22, computer.steps())
+        let computer = Computer::new(&get_variant(18, "small").unwrap(), 7, 12).unwrap();
+        assert_eq!(Some(22), computer.steps())
     }
 
     #[test]
     fn first_byte() {
-        let computer = Computer::new(&read_to_string("inputs/day18_small.txt").unwrap(), 7, 12);
-        assert_eq!((1, 6), computer.first_byte())
+        let computer = Computer::new(&get_variant(18, "small").unwrap(), 7, 12).unwrap();
+        assert_eq!(Some(Point::new(1, 6)), computer.first_byte())
+    }
+
+    #[test]
+    fn exit_never_cut_off() {
+        // Every byte has fallen by the time the path is looked for
+        let computer = Computer::new("1,1\n", 3, 1).unwrap();
+        assert_eq!(Some(4), computer.steps());
+        assert_eq!(None, computer.first_byte());
+        let computer = Computer::new("1,0\n0,1\n", 3, 2).unwrap();
+        assert_eq!(None, computer.steps());
+        assert_eq!(Some(Point::new(1, 0)), computer.first_byte());
     }
 }
@@ -1,23 +1,38 @@
-#![allow(dead_code)]
-use std::{collections::HashMap, fs::read_to_string};
+use std::collections::HashMap;
 
 use itertools::Itertools;
 
-struct HotSprings {
+use crate::{
+    error::Result,
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
+
+pub struct HotSprings {
     patterns: Vec<String>,
     designs: Vec<String>,
 }
 
 impl HotSprings {
-    fn new(input: &str) -> Self {
-        let data = input.split_once("\n\n").unwrap();
-        let patterns = data.0.split(", ").map(|x| x.to_string()).collect_vec();
-        let designs = data.1.lines().map(|x| x.to_string()).collect_vec();
-        Self { patterns, designs }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let data = input
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::eof(input, "blank line before the designs"))?;
+        let patterns = data
+            .0
+            .split(", ")
+            .map(|pattern| stripes(input, pattern, "towel pattern"))
+            .collect::<Result<_, _>>()?;
+        let designs = data
+            .1
+            .lines()
+            .map(|design| stripes(input, design, "design"))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { patterns, designs })
     }
 
-    fn design_possible(&self, partial_design: &str) -> bool {
-        if partial_design.len() == 0 {
+    pub fn design_possible(&self, partial_design: &str) -> bool {
+        if partial_design.is_empty() {
             return true;
         }
         let patterns = self
@@ -25,7 +40,7 @@ impl HotSprings {
             .iter()
             .filter(|pat| partial_design.starts_with(*pat))
             .collect_vec();
-        if patterns.len() == 0 {
+        if patterns.is_empty() {
             false
         } else {
             patterns
@@ -34,15 +49,23 @@ impl HotSprings {
         }
     }
 
-    fn count_possible_designs(&self) -> usize {
+    pub fn count_possible_designs(&self) -> usize {
         self.designs
             .iter()
             .filter(|x| self.design_possible(x))
             .count()
     }
 
+    /// [`HotSprings::count_possible_designs`] using the memoised way counts.
+    pub fn count_designs_with_ways(&self) -> usize {
+        self.designs
+            .iter()
+            .filter(|design| self.different_ways(design, &mut HashMap::new()) > 0)
+            .count()
+    }
+
     fn different_ways(&self, partial_design: &str, ways: &mut HashMap<String, usize>) -> usize {
-        if partial_design.len() == 0 {
+        if partial_design.is_empty() {
             return 1;
         }
         if let Some(count) = ways.get(partial_design) {
@@ -58,7 +81,7 @@ impl HotSprings {
         count
     }
 
-    fn sum_different_ways(&self) -> usize {
+    pub fn sum_different_ways(&self) -> usize {
         self.designs
             .iter()
             .map(|design| self.different_ways(design, &mut HashMap::new()))
@@ -66,25 +89,65 @@ impl HotSprings {
     }
 }
 
-pub fn run() {
-    let hs = HotSprings::new(&read_to_string("inputs/day19.txt").unwrap());
-    println!("Possible designs: {}", hs.count_possible_designs());
-    println!("Sum different ways: {}", hs.sum_different_ways());
+/// Checks `token`, a slice of `input`, is a non-empty run of stripe colours.
+/// An empty pattern would match forever without using up the design.
+fn stripes(input: &str, token: &str, expected: &str) -> Result<String, ParseError> {
+    if token.is_empty() {
+        return Err(ParseError::new(input, token, expected));
+    }
+    match token.char_indices().find(|(_, c)| !"wubrg".contains(*c)) {
+        Some((i, c)) => Err(ParseError::new(
+            input,
+            &token[i..i + c.len_utf8()],
+            "stripe colour `w`, `u`, `b`, `r` or `g`",
+        )),
+        None => Ok(token.to_string()),
+    }
+}
+
+impl Solution for HotSprings {
+    const DAY: usize = 19;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(HotSprings::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.count_possible_designs().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.sum_different_ways().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use super::*;
 
     #[test]
     fn count_possible() {
-        let hs = HotSprings::new(&read_to_string("inputs/day19_small.txt").unwrap());
+        let hs = HotSprings::new(&get_variant(19, "small").unwrap()).unwrap();
         assert_eq!(6, hs.count_possible_designs());
     }
 
     #[test]
     fn sum_different_ways() {
-        let hs = HotSprings::new(&read_to_string("inputs/day19_small.txt").unwrap());
+        let hs = HotSprings::new(&get_variant(19, "small").unwrap()).unwrap();
         assert_eq!(16, hs.sum_different_ways());
     }
+
+    #[test]
+    fn invalid() {
+        let err = HotSprings::new("r, \n\nrx\n").err().unwrap();
+        assert_eq!((1, 4), (err.line, err.column));
+        assert_eq!("towel pattern", err.expected);
+        let err = HotSprings::new("r, b\n\nrb\n\nbr\n").err().unwrap();
+        assert_eq!((4, 1), (err.line, err.column));
+        let err = HotSprings::new("r, b\n\nrbx\n").err().unwrap();
+        assert_eq!((3, 3), (err.line, err.column));
+        assert_eq!("`x`", err.found);
+    }
 }
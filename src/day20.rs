@@ -1,198 +1,210 @@
-#![allow(dead_code)]
-use std::{
-    cmp::Reverse,
-    collections::{BinaryHeap, HashMap, HashSet},
-    fs::read_to_string,
-};
+use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
+use tracing::instrument;
+
+use crate::{
+    cancel,
+    error::Result,
+    geometry::Point,
+    graph,
+    image::{self, Image, Picture},
+    parse::ParseError,
+    solution::{read_params, Answer, Params, Solution},
+    utils::Grid,
+};
 
 // This method also works for inputs with multiple paths and dead ends. This is
 // apparently unnecessary per the problem description but I already solved it
 // this way so whatever.
-struct Racetrack {
-    grid: HashSet<Point>,
+pub struct Racetrack {
+    grid: Grid<bool>,
     edges: HashMap<Point, Vec<Point>>,
     start: Point,
     end: Point,
+    threshold: u32,
 }
 
 impl Racetrack {
-    fn new(input: &str) -> Self {
-        let mut grid = HashSet::new();
-        let mut start = (0, 0);
-        let mut end = (0, 0);
-        for (y, line) in input.lines().enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let point = (y as isize, x as isize);
-                if c == 'S' {
-                    start = point;
-                } else if c == 'E' {
-                    end = point;
-                }
-                if c != '#' {
-                    grid.insert(point);
-                }
-            }
-        }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let chars = Grid::parse(input, "`#`, `.`, `S` or `E`", |c| {
+            matches!(c, '#' | '.' | 'S' | 'E').then_some(c)
+        })?;
+        let start = chars
+            .find(&'S')
+            .ok_or_else(|| ParseError::eof(input, "start `S`"))?;
+        let end = chars
+            .find(&'E')
+            .ok_or_else(|| ParseError::eof(input, "end `E`"))?;
+        // Whether each point is on the track
+        let grid = chars.map(|c| *c != '#');
         let edges = grid
             .iter()
-            .map(|point| {
-                let surrounding = point
-                    .surrounding()
-                    .into_iter()
-                    .filter(|sp| grid.contains(&sp))
-                    .collect_vec();
-                (*point, surrounding)
+            .filter(|(_, track)| **track)
+            .map(|(point, _)| {
+                let surrounding = grid.neighbours(point).filter(|sp| grid[*sp]).collect_vec();
+                (point, surrounding)
             })
             .collect();
-        Self {
+        let racetrack = Self {
             grid,
             edges,
             start,
             end,
+            threshold: 100,
+        };
+        if !racetrack.distances(start).contains_key(&end) {
+            let at = input.find('E').expect("the end was found");
+            return Err(ParseError::new(
+                input,
+                &input[at..at + 1],
+                "an end `E` reachable from the start",
+            ));
         }
+        Ok(racetrack)
     }
 
-    fn dijkstra(&self, start: Point) -> HashMap<Point, u32> {
-        let mut distances = HashMap::new();
-        let mut visited = HashSet::new();
-        let mut q = BinaryHeap::<(Reverse<u32>, Point)>::new();
-        distances.insert(start, 0);
-        q.push((Reverse(0), start));
+    /// The fewest picoseconds a cheat has to save for the answers to count
+    /// it, 100 unless set.
+    pub fn threshold(mut self, threshold: u32) -> Self {
+        self.threshold = threshold;
+        self
+    }
 
-        while let Some((Reverse(src_dist), src_node)) = q.pop() {
-            if visited.contains(&src_node) {
-                continue;
-            }
-            visited.insert(src_node);
-            for dst_node in self.edges.get(&src_node).unwrap() {
-                let new_dist = src_dist + 1;
-                if new_dist < *distances.entry(*dst_node).or_insert(u32::MAX) {
-                    distances.insert(*dst_node, new_dist);
-                    q.push((Reverse(new_dist), *dst_node));
-                }
-            }
-        }
+    /// Steps along the track from `start` to every point on it.
+    fn distances(&self, start: Point) -> HashMap<Point, u32> {
+        let neighbours = |p: &Point| self.edges[p].iter().map(|n| (*n, 1));
+        graph::bfs(&neighbours, [start]).into_distances()
+    }
 
-        distances
+    pub fn count_cheats(&self, threshold: u32) -> usize {
+        self.count_cheats_within(threshold, 2)
     }
 
-    fn count_cheats(&self, threshold: u32) -> usize {
-        let from_start = self.dijkstra(self.start);
-        let from_end = self.dijkstra(self.end);
-        let record = from_start[&self.end];
+    pub fn count_big_cheats(&self, threshold: u32) -> usize {
+        self.count_cheats_within(threshold, 20)
+    }
 
-        self.grid
-            .iter()
-            .filter(|point| from_start.contains_key(point))
-            .map(|point| {
-                point
-                    .cheated()
-                    .iter()
-                    .filter(|sp| {
-                        if let Some(end_dist) = from_end.get(sp) {
-                            let dist = from_start[point] + 2 + end_dist;
-                            if dist < record && record - dist >= threshold {
-                                return true;
-                            }
-                        }
-                        false
-                    })
-                    .count()
-            })
-            .sum()
+    /// Counts cheats of up to `length` steps through walls that save at least
+    /// `threshold` picoseconds.
+    fn count_cheats_within(&self, threshold: u32, length: isize) -> usize {
+        let mut count = 0;
+        self.for_each_cheat(threshold, length, |_, _| count += 1);
+        count
     }
 
-    fn count_big_cheats(&self, threshold: u32) -> usize {
-        let from_start = self.dijkstra(self.start);
-        let from_end = self.dijkstra(self.end);
+    /// Calls `f` with the start and end of every cheat of up to `length`
+    /// steps that saves at least `threshold` picoseconds.
+    #[instrument(skip(self, f))]
+    fn for_each_cheat(&self, threshold: u32, length: isize, mut f: impl FnMut(Point, Point)) {
+        let from_start = self.distances(self.start);
+        let from_end = self.distances(self.end);
         let record = from_start[&self.end];
+        let offsets = (-length..=length)
+            .flat_map(|dy| (-length..=length).map(move |dx| Point::new(dy, dx)))
+            .filter(|offset| (2..=length as usize).contains(&offset.manhattan(Point::ORIGIN)))
+            .collect_vec();
+
+        for point in self.grid.points() {
+            cancel::checkpoint();
+            let Some(start_dist) = from_start.get(&point) else {
+                continue;
+            };
+            for offset in &offsets {
+                if let Some(end_dist) = from_end.get(&(point + *offset)) {
+                    let cheated_steps = offset.manhattan(Point::ORIGIN) as u32;
+                    let dist = start_dist + cheated_steps + end_dist;
+                    if dist < record && record - dist >= threshold {
+                        f(point, point + *offset);
+                    }
+                }
+            }
+        }
+    }
+}
 
-        self.grid
-            .iter()
-            .filter(|point| from_start.contains_key(point))
-            .map(|point| {
-                point
-                    .big_cheated()
-                    .iter()
-                    .filter(|(sp, cheated_steps)| {
-                        if let Some(end_dist) = from_end.get(sp) {
-                            let dist = from_start[point] + cheated_steps + end_dist;
-                            if dist < record && record - dist >= threshold {
-                                return true;
-                            }
-                        }
-                        false
-                    })
-                    .count()
-            })
-            .sum()
+impl Picture for Racetrack {
+    /// The track with the walls that two-step cheats saving at least the
+    /// threshold pass through highlighted, and where they start and end.
+    fn picture(&self, scale: usize) -> Image {
+        let mut image = Image::from_grid(&self.grid, scale, |track| {
+            if *track {
+                image::FLOOR
+            } else {
+                image::WALL
+            }
+        });
+        let mut walls = HashSet::new();
+        let mut ends = HashSet::new();
+        self.for_each_cheat(self.threshold, 2, |from, to| {
+            walls.extend(
+                self.grid
+                    .neighbours(from)
+                    .filter(|p| !self.grid[*p] && p.manhattan(to) == 1),
+            );
+            ends.extend([from, to]);
+        });
+        image.overlay(ends, image::PATH);
+        image.overlay(walls, image::END);
+        image.paint(self.start, image::START);
+        image
     }
 }
 
-type Point = (isize, isize);
+impl Solution for Racetrack {
+    const DAY: usize = 20;
 
-trait PointStuff: Sized {
-    fn surrounding(&self) -> [Self; 4];
-    fn cheated(&self) -> [Self; 8];
-    fn big_cheated(&self) -> Vec<(Self, u32)>;
-}
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Racetrack::new(input)?)
+    }
 
-impl PointStuff for Point {
-    fn surrounding(&self) -> [Self; 4] {
-        let (y, x) = *self;
-        [(y + 1, x), (y - 1, x), (y, x + 1), (y, x - 1)]
-    }
-
-    fn cheated(&self) -> [Self; 8] {
-        let (y, x) = *self;
-        [
-            (y, x - 2),
-            (y - 1, x - 1),
-            (y - 2, x),
-            (y - 1, x + 1),
-            (y, x + 2),
-            (y + 1, x + 1),
-            (y + 2, x),
-            (y + 1, x - 1),
-        ]
-    }
-
-    fn big_cheated(&self) -> Vec<(Self, u32)> {
-        let (y, x) = *self;
-        (-20_isize..=20)
-            .flat_map(|dy| {
-                (-20_isize..=20).map(move |dx| {
-                    let dist = dy.abs() as u32 + dx.abs() as u32;
-                    ((y + dy, x + dx), dist)
-                })
-            })
-            .filter(|(_, dist)| dist > &1 && dist <= &20)
-            .collect_vec()
+    fn parse_with(input: &str, params: &Params) -> Result<Self> {
+        let [threshold] = read_params(Self::DAY, params, [("threshold", 100)])?;
+        Ok(Racetrack::new(input)?.threshold(threshold as u32))
     }
-}
 
-pub fn run() {
-    let racetrack = Racetrack::new(&read_to_string("inputs/day20.txt").unwrap());
-    println!("Cheats: {}", racetrack.count_cheats(100));
-    println!("Big cheats: {}", racetrack.count_big_cheats(100));
-    (3 as isize, 2 as isize).big_cheated();
+    fn part1(&self) -> Answer {
+        self.count_cheats(self.threshold).into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.count_big_cheats(self.threshold).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::input::get_variant;
+
     use super::*;
 
     #[test]
     fn count_cheats() {
-        let racetrack = Racetrack::new(&read_to_string("inputs/day20_small.txt").unwrap());
+        let racetrack = Racetrack::new(&get_variant(20, "small").unwrap()).unwrap();
         assert_eq!(44, racetrack.count_cheats(1))
     }
 
     #[test]
     fn count_big_cheats() {
-        let racetrack = Racetrack::new(&read_to_string("inputs/day20_small.txt").unwrap());
+        let racetrack = Racetrack::new(&get_variant(20, "small").unwrap()).unwrap();
         assert_eq!(285, racetrack.count_big_cheats(50))
     }
+
+    #[test]
+    fn picture() {
+        let racetrack = Racetrack::new(&get_variant(20, "small").unwrap()).unwrap();
+        let image = racetrack.threshold(50).picture(1);
+        // The one cheat saving 50 picoseconds goes through a single wall
+        let walls = (0..image.height())
+            .flat_map(|y| (0..image.width()).map(move |x| (x, y)))
+            .filter(|(x, y)| image.pixel(*x, *y) == Some(image::END))
+            .count();
+        assert_eq!(1, walls);
+    }
+    #[test]
+    fn unreachable_end() {
+        let e = Racetrack::new("#####\n#S#E#\n#####\n").err().unwrap();
+        assert_eq!((2, 4), (e.line, e.column));
+        assert_eq!("an end `E` reachable from the start", e.expected);
+    }
 }
@@ -1,51 +1,61 @@
-#![allow(dead_code)]
-use std::{collections::HashMap, iter::repeat};
+use std::{collections::HashMap, iter::repeat_n};
 
 use itertools::Itertools;
 use lazy_static::lazy_static;
 
-use crate::utils::{get_input, Point};
+use crate::{
+    error::Result,
+    geometry::{Direction, Point},
+    parse::{lines, number, ParseError},
+    solution::{Answer, Solution},
+};
 
 lazy_static! {
     static ref NUMERIC_BUTTONS: HashMap<char, Point> = HashMap::from([
-        ('7', (0, 0)),
-        ('8', (0, 1)),
-        ('9', (0, 2)),
-        ('4', (1, 0)),
-        ('5', (1, 1)),
-        ('6', (1, 2)),
-        ('1', (2, 0)),
-        ('2', (2, 1)),
-        ('3', (2, 2)),
-        ('0', (3, 1)),
-        ('A', (3, 2)),
+        ('7', Point::new(0, 0)),
+        ('8', Point::new(0, 1)),
+        ('9', Point::new(0, 2)),
+        ('4', Point::new(1, 0)),
+        ('5', Point::new(1, 1)),
+        ('6', Point::new(1, 2)),
+        ('1', Point::new(2, 0)),
+        ('2', Point::new(2, 1)),
+        ('3', Point::new(2, 2)),
+        ('0', Point::new(3, 1)),
+        ('A', Point::new(3, 2)),
     ]);
     static ref DIRECTIONAL_BUTTONS: HashMap<char, Point> = HashMap::from([
-        ('^', (0, 1)),
-        ('A', (0, 2)),
-        ('<', (1, 0)),
-        ('v', (1, 1)),
-        ('>', (1, 2)),
+        ('^', Point::new(0, 1)),
+        ('A', Point::new(0, 2)),
+        ('<', Point::new(1, 0)),
+        ('v', Point::new(1, 1)),
+        ('>', Point::new(1, 2)),
     ]);
 }
 
-const NUMERIC_HOLE: Point = (3, 0);
-const DIRECTIONAL_HOLE: Point = (0, 0);
+const NUMERIC_HOLE: Point = Point::new(3, 0);
+const DIRECTIONAL_HOLE: Point = Point::ORIGIN;
 
-struct Starship {
+pub struct Starship {
     codes: Vec<Vec<char>>,
 }
 
 impl Starship {
-    fn new(input: &str) -> Self {
-        let codes = input
-            .lines()
-            .map(|line| line.chars().collect_vec())
-            .collect_vec();
-        Self { codes }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let codes = lines(input, |line| {
+            let digits = line
+                .strip_suffix("A")
+                .ok_or_else(|| ParseError::eof(line, "`A`"))?;
+            if let Some((i, c)) = digits.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+                return Err(ParseError::new(line, &digits[i..i + c.len_utf8()], "digit"));
+            }
+            number::<u64>(line, digits)?;
+            Ok(line.chars().collect_vec())
+        })?;
+        Ok(Self { codes })
     }
 
-    fn complexity(&self, directional_robots: usize) -> u64 {
+    pub fn complexity(&self, directional_robots: usize) -> u64 {
         self.codes
             .iter()
             .map(|code| {
@@ -64,14 +74,14 @@ impl Starship {
 }
 
 fn numeric_presses(code: &Vec<char>, robots: usize) -> usize {
-    let mut arm = (3, 2);
+    let mut arm = NUMERIC_BUTTONS[&'A'];
     let mut length = 0;
     let mut cache = HashMap::new();
     for c in code {
         let target = NUMERIC_BUTTONS[c];
-        length += if (arm.0 + target.0 - arm.0, arm.1) == NUMERIC_HOLE {
+        length += if Point::new(target.y, arm.x) == NUMERIC_HOLE {
             directional_presses(&presses(false, &target, &arm), 0, &mut cache, robots)
-        } else if (arm.0, arm.1 + target.1 - arm.1) == NUMERIC_HOLE {
+        } else if Point::new(arm.y, target.x) == NUMERIC_HOLE {
             directional_presses(&presses(true, &target, &arm), 0, &mut cache, robots)
         } else {
             directional_presses(&presses(false, &target, &arm), 0, &mut cache, robots).min(
@@ -95,13 +105,13 @@ fn directional_presses(
     if let Some(v) = cache.get(&(code.to_owned(), depth)) {
         return *v;
     }
-    let mut arm = (0, 2);
+    let mut arm = DIRECTIONAL_BUTTONS[&'A'];
     let mut length = 0;
     for c in code {
         let target = DIRECTIONAL_BUTTONS[c];
-        length += if (target.0, arm.1) == DIRECTIONAL_HOLE {
+        length += if Point::new(target.y, arm.x) == DIRECTIONAL_HOLE {
             directional_presses(&presses(false, &target, &arm), depth + 1, cache, robots)
-        } else if (arm.0, target.1) == DIRECTIONAL_HOLE {
+        } else if Point::new(arm.y, target.x) == DIRECTIONAL_HOLE {
             directional_presses(&presses(true, &target, &arm), depth + 1, cache, robots)
         } else {
             directional_presses(&presses(false, &target, &arm), depth + 1, cache, robots).min(
@@ -115,12 +125,19 @@ fn directional_presses(
 }
 
 fn presses(y_first: bool, target: &Point, arm: &Point) -> Vec<char> {
-    let yd = target.0 - arm.0;
-    let c = if yd >= 0 { 'v' } else { '^' };
-    let ys = repeat(c).take(yd.abs() as usize);
-    let xd = target.1 - arm.1;
-    let c = if xd >= 0 { '>' } else { '<' };
-    let xs = repeat(c).take(xd.abs() as usize);
+    let d = *target - *arm;
+    let c = if d.y >= 0 {
+        Direction::Down
+    } else {
+        Direction::Up
+    };
+    let ys = repeat_n(c.arrow(), d.y.unsigned_abs());
+    let c = if d.x >= 0 {
+        Direction::Right
+    } else {
+        Direction::Left
+    };
+    let xs = repeat_n(c.arrow(), d.x.unsigned_abs());
     if y_first {
         ys.chain(xs).chain(['A']).collect_vec()
     } else {
@@ -128,21 +145,31 @@ fn presses(y_first: bool, target: &Point, arm: &Point) -> Vec<char> {
     }
 }
 
-pub fn run() {
-    let starship = Starship::new(&get_input(21));
-    println!("Complexity: {}", starship.complexity(2));
-    println!("Big Complexity: {}", starship.complexity(25));
+impl Solution for Starship {
+    const DAY: usize = 21;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Starship::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.complexity(2).into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.complexity(25).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::get_small;
+    use crate::input::get_variant;
 
     use super::*;
 
     #[test]
     fn complexity() {
-        let starship = Starship::new(&get_small(21));
+        let starship = Starship::new(&get_variant(21, "small").unwrap()).unwrap();
         assert_eq!(126384, starship.complexity(2));
     }
 }
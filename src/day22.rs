@@ -1,27 +1,27 @@
-#![allow(dead_code)]
 use std::collections::{HashMap, HashSet, VecDeque};
 
-use itertools::Itertools;
+use crate::{
+    cancel,
+    error::Result,
+    parse::{lines, number, ParseError},
+    solution::{Answer, Solution},
+};
 
-use crate::utils::get_input;
-
-struct MonkeyMarket {
+pub struct MonkeyMarket {
     secrets: Vec<i64>,
 }
 
 impl MonkeyMarket {
-    fn new(input: &str) -> Self {
-        let secrets = input
-            .lines()
-            .map(|line| line.parse().unwrap())
-            .collect_vec();
-        Self { secrets }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let secrets = lines(input, |line| number(line, line))?;
+        Ok(Self { secrets })
     }
 
-    fn sum_2000s(&self) -> i64 {
+    pub fn sum_2000s(&self) -> i64 {
         self.secrets
             .iter()
             .map(|secret| {
+                cancel::checkpoint();
                 let mut secret = *secret;
                 for _ in 0..2000 {
                     secret = MonkeyMarket::transform(secret);
@@ -31,7 +31,7 @@ impl MonkeyMarket {
             .sum()
     }
 
-    fn transform(secret: i64) -> i64 {
+    pub fn transform(secret: i64) -> i64 {
         let mut secret = MonkeyMarket::prune(MonkeyMarket::mix(secret, secret * 64));
         secret = MonkeyMarket::mix(secret, secret / 32);
         secret = MonkeyMarket::prune(MonkeyMarket::mix(secret, secret * 2048));
@@ -46,9 +46,10 @@ impl MonkeyMarket {
         secret % 16777216
     }
 
-    fn most_bananas(&self) -> i64 {
+    pub fn most_bananas(&self) -> i64 {
         let mut differences_bananas = HashMap::new();
         for secret in self.secrets.iter() {
+            cancel::checkpoint();
             let mut last_secret = *secret;
             let mut last_digit = last_secret % 10;
             let mut differences = VecDeque::new();
@@ -84,27 +85,37 @@ impl MonkeyMarket {
     }
 }
 
-pub fn run() {
-    let mm = MonkeyMarket::new(&get_input(22));
-    println!("2000th secret numbers sum: {}", mm.sum_2000s());
-    println!("Most bananas: {}", mm.most_bananas());
+impl Solution for MonkeyMarket {
+    const DAY: usize = 22;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(MonkeyMarket::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.sum_2000s().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.most_bananas().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::utils::{get_small, get_smalln};
+    use crate::input::get_variant;
 
     #[test]
     fn sum() {
-        let mm = MonkeyMarket::new(&get_small(22));
+        let mm = MonkeyMarket::new(&get_variant(22, "small").unwrap()).unwrap();
         assert_eq!(37327623, mm.sum_2000s());
     }
 
     #[test]
     fn most_bananas() {
-        let mm = MonkeyMarket::new(&get_smalln(22, 2));
+        let mm = MonkeyMarket::new(&get_variant(22, "small2").unwrap()).unwrap();
         assert_eq!(23, mm.most_bananas());
     }
 }
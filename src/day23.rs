@@ -1,84 +1,54 @@
-#![allow(dead_code)]
 use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-use crate::utils::get_input;
+use crate::{
+    error::Result,
+    graph,
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
 
-struct LanParty {
-    edges: HashMap<String, Vec<String>>,
+pub struct LanParty {
+    edges: HashMap<String, HashSet<String>>,
 }
 
 impl LanParty {
-    fn new(input: &str) -> Self {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
         let mut edges = HashMap::new();
         for line in input.lines() {
-            let (a, b) = line.split_once("-").unwrap();
+            let (a, b) = line
+                .split_once("-")
+                .ok_or_else(|| ParseError::eof(line, "`-`").within(input, line))?;
             for (a, b) in [(a, b), (b, a)] {
                 edges
                     .entry(a.to_owned())
-                    .or_insert(Vec::new())
-                    .push(b.to_owned());
+                    .or_insert(HashSet::new())
+                    .insert(b.to_owned());
             }
         }
-        Self { edges }
+        Ok(Self { edges })
     }
 
-    fn password(&self) -> String {
-        let mut max_clique = HashSet::new();
-        self.bron_kerbosch(
-            &mut max_clique,
-            HashSet::new(),
-            self.edges.keys().cloned().collect::<HashSet<_>>(),
-            HashSet::new(),
-        );
-        max_clique.iter().sorted().join(",")
-    }
-
-    fn bron_kerbosch(
-        &self,
-        max_clique: &mut HashSet<String>,
-        r: HashSet<String>,
-        p: HashSet<String>,
-        x: HashSet<String>,
-    ) {
-        if p.is_empty() && x.is_empty() {
-            if r.len() > max_clique.len() {
-                *max_clique = r;
-            }
-            return;
-        }
-        let mut p = p;
-        let mut x = x;
-        let u = p.union(&x).into_iter().next().unwrap();
-        for v in p
-            .clone()
-            .difference(&self.edges[u].iter().cloned().collect::<HashSet<_>>())
-        {
-            let n = &self.edges[v].iter().cloned().collect::<HashSet<_>>();
-            let mut r = r.clone();
-            r.insert(v.to_string());
-            self.bron_kerbosch(
-                max_clique,
-                r,
-                p.clone().intersection(n).into_iter().cloned().collect(),
-                x.clone().intersection(n).into_iter().cloned().collect(),
-            );
-            p.remove(v);
-            x.insert(v.to_string());
-        }
+    pub fn password(&self) -> String {
+        graph::maximal_cliques(&self.edges)
+            .into_iter()
+            .max_by_key(|clique| clique.len())
+            .unwrap_or_default()
+            .iter()
+            .sorted()
+            .join(",")
     }
 
-    fn t_cliques(&self) -> usize {
+    pub fn t_cliques(&self) -> usize {
         let mut starting = HashSet::new();
         self.edges
             .keys()
-            .into_iter()
             .filter(|comp| comp.starts_with("t"))
             .cloned()
             .flat_map(|k| {
                 let res =
-                    self.interconnected(Vec::from([k.to_string()]), HashSet::new(), &mut starting);
+                    self.interconnected(Vec::from([k.to_string()]), HashSet::new(), &starting);
                 starting.insert(k.to_string());
                 res
             })
@@ -97,11 +67,8 @@ impl LanParty {
         }
 
         let mut candidates = candidates;
-        let neighbors = self.edges[clique.last().unwrap()]
-            .iter()
-            .cloned()
-            .collect::<HashSet<_>>();
-        if candidates.len() == 0 {
+        let neighbors = self.edges[clique.last().unwrap()].clone();
+        if candidates.is_empty() {
             candidates.extend(neighbors);
         } else {
             candidates = candidates
@@ -122,19 +89,29 @@ impl LanParty {
     }
 }
 
-pub fn run() {
-    let lp = LanParty::new(&get_input(23));
-    println!("T cliques of 3: {}", lp.t_cliques());
-    println!("Password: {}", lp.password());
+impl Solution for LanParty {
+    const DAY: usize = 23;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(LanParty::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.t_cliques().into()
+    }
+
+    fn part2(&self) -> Answer {
+        self.password().into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{day23::LanParty, utils::get_small};
+    use crate::{day23::LanParty, input::get_variant};
 
     #[test]
     fn t_cliques() {
-        let lp = LanParty::new(&get_small(23));
+        let lp = LanParty::new(&get_variant(23, "small").unwrap()).unwrap();
         assert_eq!(7, lp.t_cliques())
     }
 }
@@ -1,52 +1,83 @@
-#![allow(dead_code)]
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-use crate::utils::get_input;
+use crate::{
+    error::Result,
+    parse::{lines, next, ParseError},
+    solution::{Answer, Solution},
+};
 
-struct Grove {
+pub struct Grove {
     values: HashMap<String, bool>,
     gates: HashMap<String, Gate>,
 }
 
 impl Grove {
-    fn new(input: &str) -> Self {
-        let (wires, gates) = input.split_once("\n\n").unwrap();
-        let values = wires
-            .lines()
-            .map(|line| line.split_once(": ").unwrap())
-            .map(|(id, val)| {
-                let val = match val {
-                    "1" => true,
-                    "0" => false,
-                    _ => unreachable!(),
-                };
-                (id.to_owned(), val)
-            })
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let (wires, gates) = input
+            .split_once("\n\n")
+            .ok_or_else(|| ParseError::eof(input, "blank line before the gates"))?;
+        let values = lines(wires, |line| {
+            let (id, val) = line
+                .split_once(": ")
+                .ok_or_else(|| ParseError::eof(line, "`: `"))?;
+            let val = match val {
+                "1" => true,
+                "0" => false,
+                _ => return Err(ParseError::new(line, val, "`0` or `1`")),
+            };
+            Ok((id.to_owned(), val))
+        })?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+        let parsed = lines(gates, |line| {
+            let mut data = line.split_whitespace();
+            let left = next(line, &mut data, "wire")?;
+            let op = next(line, &mut data, "operator")?;
+            let op = match op {
+                "AND" => Operator::And,
+                "OR" => Operator::Or,
+                "XOR" => Operator::Xor,
+                _ => return Err(ParseError::new(line, op, "`AND`, `OR` or `XOR`")),
+            };
+            let right = next(line, &mut data, "wire")?;
+            let arrow = next(line, &mut data, "`->`")?;
+            if arrow != "->" {
+                return Err(ParseError::new(line, arrow, "`->`"));
+            }
+            Ok((next(line, &mut data, "wire")?, [left, right], op))
+        })
+        .map_err(|e| e.within(input, gates))?;
+
+        // Every input has to be a starting wire or a gate's output, and no
+        // gate may depend on its own output, or resolving it never finishes
+        let inputs = parsed
+            .iter()
+            .map(|(output, inputs, _)| (*output, *inputs))
             .collect::<HashMap<_, _>>();
-        let gates = gates
-            .lines()
-            .map(|line| {
-                let data = line.split_whitespace().collect_vec();
-                let op = match data[1] {
-                    "AND" => Operator::And,
-                    "OR" => Operator::Or,
-                    "XOR" => Operator::Xor,
-                    _ => unreachable!(),
-                };
+        for wire in parsed.iter().flat_map(|(_, inputs, _)| inputs) {
+            if !values.contains_key(*wire) && !inputs.contains_key(wire) {
+                return Err(ParseError::new(input, wire, "a defined wire"));
+            }
+        }
+        check_acyclic(input, &inputs)?;
+
+        let gates = parsed
+            .into_iter()
+            .map(|(output, [left, right], op)| {
                 let gate = Gate {
-                    left: data[0].to_string(),
-                    right: data[2].to_string(),
+                    left: left.to_string(),
+                    right: right.to_string(),
                     op,
                 };
-                (data[4].to_owned(), gate)
+                (output.to_owned(), gate)
             })
             .collect::<HashMap<_, _>>();
-        Self { values, gates }
+        Ok(Self { values, gates })
     }
 
-    fn dot(&self) -> String {
+    pub fn dot(&self) -> String {
         let inner = self
             .gates
             .iter()
@@ -88,7 +119,7 @@ impl Grove {
         res
     }
 
-    fn z_decimal(&self) -> u64 {
+    pub fn z_decimal(&self) -> u64 {
         let mut values = self.values.clone();
         let binary = self
             .gates
@@ -108,6 +139,43 @@ impl Grove {
     }
 }
 
+/// Fails at the first gate input, a slice of `input`, that leads back to the
+/// gate itself. `inputs` maps each gate's output wire to its two inputs.
+fn check_acyclic(input: &str, inputs: &HashMap<&str, [&str; 2]>) -> Result<(), ParseError> {
+    let mut done = HashSet::new();
+    let mut path = HashSet::new();
+    for root in inputs.keys().sorted() {
+        if done.contains(root) {
+            continue;
+        }
+        // Depth first, without recursing, so long chains can't overflow
+        let mut stack = vec![(*root, 0)];
+        path.insert(*root);
+        while let Some((wire, i)) = stack.last_mut() {
+            let wire = *wire;
+            let Some(next) = inputs[wire].get(*i).copied() else {
+                path.remove(wire);
+                done.insert(wire);
+                stack.pop();
+                continue;
+            };
+            *i += 1;
+            if path.contains(next) {
+                return Err(ParseError::new(
+                    input,
+                    next,
+                    format!("a wire that doesn't depend on `{}`", wire),
+                ));
+            }
+            if inputs.contains_key(next) && !done.contains(next) {
+                path.insert(next);
+                stack.push((next, 0));
+            }
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 struct Gate {
     left: String,
@@ -122,21 +190,60 @@ enum Operator {
     Xor,
 }
 
-pub fn run() {
-    let grove = Grove::new(&get_input(24));
-    // println!("Z decimal: {}", grove.z_decimal());
-    println!("{}", grove.dot())
+impl Solution for Grove {
+    const DAY: usize = 24;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Grove::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.z_decimal().into()
+    }
+
+    // Found by inspecting the adder drawn by `dot`
+    fn part2(&self) -> Answer {
+        Answer::None
+    }
+
+    fn artifacts(&self) -> Vec<(&'static str, String)> {
+        vec![("dot", self.dot())]
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::get_small;
+    use crate::input::get_variant;
 
     use super::Grove;
 
     #[test]
     fn z_decimal() {
-        let grove = Grove::new(&get_small(24));
+        let grove = Grove::new(&get_variant(24, "small").unwrap()).unwrap();
         assert_eq!(2024, grove.z_decimal())
     }
+
+    #[test]
+    fn invalid_wire_value() {
+        let err = Grove::new("x00: 1\ny00: 2\n\nx00 AND y00 -> z00")
+            .err()
+            .unwrap();
+        assert_eq!((2, 6), (err.line, err.column));
+        assert_eq!("`0` or `1`", err.expected);
+    }
+
+    #[test]
+    fn undefined_wire() {
+        let err = Grove::new("x00: 1\n\nx00 AND y00 -> z00\n").err().unwrap();
+        assert_eq!((3, 9), (err.line, err.column));
+        assert_eq!("a defined wire", err.expected);
+    }
+
+    #[test]
+    fn cycle() {
+        let input = "x00: 1\n\nq00 AND x00 -> z00\nz00 OR x00 -> q00\n";
+        let err = Grove::new(input).err().unwrap();
+        assert_eq!((3, 1), (err.line, err.column));
+        assert_eq!("a wire that doesn't depend on `z00`", err.expected);
+    }
 }
@@ -1,30 +1,50 @@
-#![allow(dead_code)]
 use itertools::Itertools;
 
-use crate::utils::get_input;
+use crate::{
+    error::Result,
+    parse::{blocks, next, ParseError},
+    solution::{Answer, Solution},
+};
 
 const INVALID_HEIGHT: i32 = 6;
 const WIDTH: usize = 5;
 const HEIGHT: usize = 7;
 
-struct Office {
+pub struct Office {
     keys: Vec<Vec<i32>>,
     locks: Vec<Vec<i32>>,
 }
 
 impl Office {
-    fn new(input: &str) -> Self {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
         let mut keys = Vec::new();
         let mut locks = Vec::new();
-        for block in input.split("\n\n") {
-            let mut grid = (0..WIDTH)
-                .map(|_| (0..HEIGHT).map(|_| '0').collect_vec())
-                .collect_vec();
-            for (y, line) in block.lines().enumerate() {
-                for (x, c) in line.chars().enumerate() {
-                    grid[x][y] = c;
+        let grids = blocks(input, |block| {
+            let mut grid = vec![Vec::new(); WIDTH];
+            let mut rows = block.lines();
+            for _ in 0..HEIGHT {
+                let line = next(block, &mut rows, "schematic row")?;
+                let mut cells = line.char_indices();
+                for column in grid.iter_mut() {
+                    let (i, c) = cells
+                        .next()
+                        .ok_or_else(|| ParseError::eof(line, "`#` or `.`").within(block, line))?;
+                    if c != '#' && c != '.' {
+                        let token = &line[i..i + c.len_utf8()];
+                        return Err(ParseError::new(block, token, "`#` or `.`"));
+                    }
+                    column.push(c);
+                }
+                if let Some((i, _)) = cells.next() {
+                    return Err(ParseError::new(block, &line[i..], "end of row"));
                 }
             }
+            if let Some(line) = rows.next() {
+                return Err(ParseError::new(block, line, "end of schematic"));
+            }
+            Ok(grid)
+        })?;
+        for grid in grids {
             let heights = grid
                 .iter()
                 .map(|line| line.iter().filter(|c| **c == '#').count() as i32 - 1)
@@ -35,10 +55,10 @@ impl Office {
                 keys.push(heights);
             }
         }
-        Self { keys, locks }
+        Ok(Self { keys, locks })
     }
 
-    fn unique_pairs(&self) -> usize {
+    pub fn unique_pairs(&self) -> usize {
         let mut count = 0;
         for lock in self.locks.iter() {
             for key in self.keys.iter() {
@@ -56,20 +76,32 @@ impl Office {
     }
 }
 
-pub fn run() {
-    let office = Office::new(&get_input(25));
-    println!("Unique pairs: {}", office.unique_pairs())
+impl Solution for Office {
+    const DAY: usize = 25;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Office::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        self.unique_pairs().into()
+    }
+
+    fn part2(&self) -> Answer {
+        // There is no second puzzle on the last day
+        Answer::None
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::get_small;
+    use crate::input::get_variant;
 
     use super::Office;
 
     #[test]
     fn unique_pairs() {
-        let office = Office::new(&get_small(25));
+        let office = Office::new(&get_variant(25, "small").unwrap()).unwrap();
         assert_eq!(3, office.unique_pairs())
     }
 }
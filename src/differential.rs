@@ -0,0 +1,165 @@
+use std::{
+    ops::{Range, RangeInclusive},
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use crate::{
+    day06, day18, day19,
+    error::{panic_message, Result},
+    generate::generate,
+    solution::Answer,
+};
+
+/// Computes an answer from a puzzle input.
+pub type Check = fn(&str) -> Result<Answer>;
+
+/// Two ways of computing the same answer for a day: a slow but obviously
+/// correct reference, and the optimized version the day actually uses.
+pub struct Pair {
+    pub day: usize,
+    pub name: &'static str,
+    /// Generator sizes to try; brute force references are kept small.
+    pub sizes: RangeInclusive<usize>,
+    pub reference: Check,
+    pub optimized: Check,
+}
+
+pub const PAIRS: [Pair; 3] = [
+    Pair {
+        day: 6,
+        name: "obstructions",
+        sizes: 1..=12,
+        reference: |input| {
+            Ok(day06::Map::new(input)?
+                .different_obstructions_brute_force()
+                .into())
+        },
+        optimized: |input| Ok(day06::Map::new(input)?.different_obstructions().into()),
+    },
+    Pair {
+        day: 18,
+        name: "first byte",
+        // Bytes kept past the one that cuts off the exit
+        sizes: 1..=3,
+        reference: |input| {
+            let computer = day18::Computer::new(input, 71, 1024)?;
+            // Bisected with the standard library rather than by hand
+            let counts = (1024..computer.byte_count()).collect::<Vec<_>>();
+            let cut = counts.partition_point(|count| computer.steps_after(count + 1).is_some());
+            Ok(input
+                .lines()
+                .nth(1024 + cut)
+                .map_or(Answer::None, Answer::from))
+        },
+        optimized: |input| {
+            let fb = day18::Computer::new(input, 71, 1024)?.first_byte();
+            Ok(fb.map_or(Answer::None, |fb| format!("{},{}", fb.x, fb.y).into()))
+        },
+    },
+    Pair {
+        day: 19,
+        name: "possible designs",
+        sizes: 1..=8,
+        reference: |input| {
+            Ok(day19::HotSprings::new(input)?
+                .count_designs_with_ways()
+                .into())
+        },
+        optimized: |input| {
+            Ok(day19::HotSprings::new(input)?
+                .count_possible_designs()
+                .into())
+        },
+    },
+];
+
+/// What a solver produced: its answer, or why it didn't give one.
+pub type Outcome = std::result::Result<Answer, String>;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Disagreement {
+    pub seed: u64,
+    pub size: usize,
+    pub input: String,
+    pub reference: Outcome,
+    pub optimized: Outcome,
+}
+
+/// The result of comparing a pair over many generated inputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Comparison {
+    pub inputs: usize,
+    /// The shortest input the two solvers disagree on, if any.
+    pub smallest: Option<Disagreement>,
+}
+
+fn outcome(check: Check, input: &str) -> Outcome {
+    catch_unwind(AssertUnwindSafe(|| check(input)))
+        .map_err(|payload| format!("panicked: {}", panic_message(&*payload)))?
+        .map_err(|e| e.to_string())
+}
+
+impl Pair {
+    /// Runs both solvers on an input generated for every size and seed.
+    pub fn compare(&self, seeds: Range<u64>) -> Comparison {
+        let mut inputs = 0;
+        let mut smallest: Option<Disagreement> = None;
+        for size in self.sizes.clone() {
+            for seed in seeds.clone() {
+                let input = generate(self.day, size, seed).expect("every day has a generator");
+                inputs += 1;
+                if smallest
+                    .as_ref()
+                    .is_some_and(|d| d.input.len() <= input.len())
+                {
+                    continue;
+                }
+                let reference = outcome(self.reference, &input);
+                let optimized = outcome(self.optimized, &input);
+                if reference != optimized {
+                    smallest = Some(Disagreement {
+                        seed,
+                        size,
+                        input,
+                        reference,
+                        optimized,
+                    });
+                }
+            }
+        }
+        Comparison { inputs, smallest }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pairs_agree() {
+        for pair in PAIRS {
+            let comparison = pair.compare(0..2);
+            assert_eq!(None, comparison.smallest, "day {} {}", pair.day, pair.name);
+        }
+    }
+
+    #[test]
+    fn smallest_disagreement() {
+        let pair = Pair {
+            day: 1,
+            name: "broken",
+            sizes: 1..=5,
+            reference: |input| Ok(input.lines().count().into()),
+            optimized: |input| match input.lines().count() {
+                count if count >= 3 => Ok((count + 1).into()),
+                count => Ok(count.into()),
+            },
+        };
+        let comparison = pair.compare(0..3);
+        assert_eq!(15, comparison.inputs);
+        let smallest = comparison.smallest.unwrap();
+        assert_eq!(3, smallest.size);
+        assert_eq!(Ok(Answer::UInt(3)), smallest.reference);
+        assert_eq!(Ok(Answer::UInt(4)), smallest.optimized);
+    }
+}
@@ -0,0 +1,70 @@
+use std::{any::Any, fmt, time::Duration};
+
+use crate::parse::ParseError;
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    Parse(ParseError),
+    Input {
+        source: String,
+        reason: String,
+    },
+    /// A puzzle parameter the day doesn't take.
+    Param {
+        day: usize,
+        name: String,
+    },
+    /// The solver ran past its time budget.
+    TimedOut(Duration),
+}
+
+impl Error {
+    /// Tags parse errors with the day whose input they came from.
+    pub fn with_day(self, day: usize) -> Self {
+        match self {
+            Self::Parse(e) => Self::Parse(e.with_day(day)),
+            e => e,
+        }
+    }
+
+    /// The error as shown to a user, with parse errors pointing at where in
+    /// `input` they happened.
+    pub fn describe(self, input: &str) -> String {
+        match self {
+            Self::Parse(e) => format!("invalid input\n{}", e.render(input)),
+            e => e.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "{}", e),
+            Self::Input { source, reason } => write!(f, "could not read {}: {}", source, reason),
+            Self::Param { day, name } => {
+                write!(f, "day {} has no puzzle parameter `{}`", day, name)
+            }
+            Self::TimedOut(budget) => write!(f, "timed out after {:?}", budget),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(value: ParseError) -> Self {
+        Self::Parse(value)
+    }
+}
+
+/// The message a panic was raised with, if it had one.
+pub fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause")
+}
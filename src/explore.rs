@@ -0,0 +1,460 @@
+use std::{
+    io::{self, BufRead, Write},
+    panic::{catch_unwind, AssertUnwindSafe},
+    str::FromStr,
+};
+
+use itertools::Itertools;
+
+use crate::{
+    day02, day17, day18,
+    error::{panic_message, Result},
+    input::Source,
+    solution::{Params, Solution, YEAR},
+    verify::Fixture,
+};
+
+/// Something that can be asked of a day's parsed model from the REPL.
+pub struct Command<T> {
+    pub name: &'static str,
+    /// Arguments, `<required>` then `[optional]`, separated by spaces.
+    pub usage: &'static str,
+    pub about: &'static str,
+    pub run: fn(&T, &[&str]) -> std::result::Result<String, String>,
+}
+
+/// A day whose parsed model can be explored in the REPL.
+pub trait Explore: Solution + 'static {
+    const COMMANDS: &'static [Command<Self>];
+}
+
+/// Argument `i`, or `None` if it wasn't given.
+pub fn arg<T: FromStr>(
+    args: &[&str],
+    i: usize,
+    name: &str,
+) -> std::result::Result<Option<T>, String> {
+    args.get(i)
+        .map(|arg| {
+            arg.parse()
+                .map_err(|_| format!("`{}` isn't a valid {}", arg, name))
+        })
+        .transpose()
+}
+
+impl Explore for day02::UnusualData {
+    const COMMANDS: &'static [Command<Self>] = &[
+        Command {
+            name: "report",
+            usage: "<n>",
+            about: "levels of report n and whether it's safe",
+            run: |data, args| {
+                let n = arg(args, 0, "report number")?.unwrap();
+                let levels = data
+                    .report(n)
+                    .ok_or_else(|| format!("there is no report {}", n))?;
+                let safety = match data.dampened_reports().iter().find(|(r, _)| *r == n) {
+                    Some((_, level)) => format!("safe without level {}", level),
+                    None if data.safe(n) => "safe".to_string(),
+                    None => "unsafe".to_string(),
+                };
+                Ok(format!("{} {}", levels.iter().join(" "), safety))
+            },
+        },
+        Command {
+            name: "dampened",
+            usage: "",
+            about: "reports that are only safe because the dampener removes a level",
+            run: |data, _| {
+                Ok(data
+                    .dampened_reports()
+                    .into_iter()
+                    .map(|(report, level)| {
+                        let levels = data.report(report).unwrap();
+                        format!(
+                            "report {}: {} (without level {})",
+                            report,
+                            levels.iter().join(" "),
+                            level
+                        )
+                    })
+                    .join("\n"))
+            },
+        },
+    ];
+}
+
+impl Explore for day17::Cpu {
+    const COMMANDS: &'static [Command<Self>] = &[
+        Command {
+            name: "registers",
+            usage: "",
+            about: "starting values of registers A, B and C",
+            run: |cpu, _| {
+                let (a, b, c) = cpu.registers();
+                Ok(format!("A={} B={} C={}", a, b, c))
+            },
+        },
+        Command {
+            name: "disassemble",
+            usage: "",
+            about: "the program as pseudocode",
+            run: |cpu, _| Ok(cpu.disassemble().join("\n")),
+        },
+        Command {
+            name: "run",
+            usage: "[a]",
+            about: "output of the program, starting with register A set to a",
+            run: |cpu, args| {
+                let a = arg(args, 0, "register value")?.unwrap_or(cpu.registers().0);
+                Ok(cpu.run_with_ra(a).iter().join(","))
+            },
+        },
+        Command {
+            name: "quine",
+            usage: "",
+            about: "lowest value of register A that makes the program output itself",
+            run: |cpu, _| {
+                cpu.find_recursive_ra()
+                    .map(|ra| ra.to_string())
+                    .ok_or_else(|| {
+                        "no value of register A makes the program output itself".to_string()
+                    })
+            },
+        },
+    ];
+}
+
+impl Explore for day18::Computer {
+    const COMMANDS: &'static [Command<Self>] = &[
+        Command {
+            name: "steps",
+            usage: "<bytes>",
+            about: "fewest steps to the exit after that many bytes have fallen",
+            run: |computer, args| {
+                let count = bytes(computer, args)?;
+                Ok(match computer.steps_after(count) {
+                    Some(steps) => steps.to_string(),
+                    None => "the exit can't be reached".to_string(),
+                })
+            },
+        },
+        Command {
+            name: "map",
+            usage: "<bytes>",
+            about: "the memory space and a shortest path after that many bytes have fallen",
+            run: |computer, args| {
+                let map = computer.map(bytes(computer, args)?).to_string();
+                Ok(map.trim_end().to_string())
+            },
+        },
+        Command {
+            name: "first-byte",
+            usage: "",
+            about: "the first byte that cuts off the exit",
+            run: |computer, _| {
+                computer
+                    .first_byte()
+                    .map(|fb| format!("{},{}", fb.x, fb.y))
+                    .ok_or_else(|| "the exit is never cut off".to_string())
+            },
+        },
+    ];
+}
+
+fn bytes(computer: &day18::Computer, args: &[&str]) -> std::result::Result<usize, String> {
+    let count = arg(args, 0, "byte count")?.unwrap();
+    if count > computer.byte_count() {
+        return Err(format!("only {} bytes fall", computer.byte_count()));
+    }
+    Ok(count)
+}
+
+/// A loaded model, with its commands looked up by name.
+trait Model {
+    fn day(&self) -> usize;
+    fn help(&self) -> Vec<(&'static str, &'static str, &'static str)>;
+    fn run(&self, name: &str, args: &[&str]) -> Option<std::result::Result<String, String>>;
+}
+
+impl<E: Explore> Model for E {
+    fn day(&self) -> usize {
+        E::DAY
+    }
+
+    fn help(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        E::COMMANDS
+            .iter()
+            .map(|command| (command.name, command.usage, command.about))
+            .collect()
+    }
+
+    fn run(&self, name: &str, args: &[&str]) -> Option<std::result::Result<String, String>> {
+        match name {
+            "part1" => return Some(Ok(self.part1().to_string())),
+            "part2" => return Some(Ok(self.part2().to_string())),
+            _ => {}
+        }
+        let command = E::COMMANDS.iter().find(|command| command.name == name)?;
+        Some(check_args(name, command.usage, args).and_then(|()| (command.run)(self, args)))
+    }
+}
+
+/// Checks that the number of arguments matches `usage`.
+fn check_args(name: &str, usage: &str, args: &[&str]) -> std::result::Result<(), String> {
+    let words = usage.split_whitespace().collect_vec();
+    let required = words.iter().filter(|w| w.starts_with('<')).count();
+    if (required..=words.len()).contains(&args.len()) {
+        Ok(())
+    } else {
+        Err(format!("usage: {} {}", name, usage).trim_end().to_string())
+    }
+}
+
+type Loader = fn(&str, &Params) -> Result<Box<dyn Model>>;
+
+fn loader<E: Explore>(input: &str, params: &Params) -> Result<Box<dyn Model>> {
+    match E::parse_with(input, params) {
+        Ok(model) => Ok(Box::new(model)),
+        Err(e) => Err(e.with_day(E::DAY)),
+    }
+}
+
+const MODELS: [(usize, Loader); 3] = [
+    (2, loader::<day02::UnusualData>),
+    (17, loader::<day17::Cpu>),
+    (18, loader::<day18::Computer>),
+];
+
+/// Days that can be explored.
+pub fn days() -> impl Iterator<Item = usize> {
+    MODELS.iter().map(|(day, _)| *day)
+}
+
+/// What the REPL should do after a line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Reply {
+    Output(String),
+    Quit,
+}
+
+/// The state of a REPL: which day's model is loaded.
+#[derive(Default)]
+pub struct Session {
+    model: Option<Box<dyn Model>>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses `input` as `day`'s model with the puzzle parameters in
+    /// `params`, replacing any loaded one.
+    pub fn load(
+        &mut self,
+        day: usize,
+        input: &str,
+        params: &Params,
+    ) -> std::result::Result<(), String> {
+        let (_, loader) = MODELS.iter().find(|(d, _)| *d == day).ok_or_else(|| {
+            format!(
+                "day {} can't be explored, try {}",
+                day,
+                days().map(|d| d.to_string()).join(", ")
+            )
+        })?;
+        self.model = Some(loader(input, params).map_err(|e| e.describe(input))?);
+        Ok(())
+    }
+
+    pub fn prompt(&self) -> String {
+        match &self.model {
+            Some(model) => format!("day{:02}> ", model.day()),
+            None => "> ".to_string(),
+        }
+    }
+
+    /// Runs one line of input.
+    pub fn execute(&mut self, line: &str) -> std::result::Result<Reply, String> {
+        let words = line.split_whitespace().collect_vec();
+        let Some((&name, args)) = words.split_first() else {
+            return Ok(Reply::Output(String::new()));
+        };
+        match name {
+            "quit" | "exit" => return Ok(Reply::Quit),
+            "help" => return Ok(Reply::Output(self.help())),
+            "load" => {
+                check_args(name, "<day> [variant]", args)?;
+                let day = arg(args, 0, "day")?.unwrap();
+                // Examples bring the puzzle parameters in their sidecars
+                let (source, params) = match args.get(1) {
+                    Some(variant) => (
+                        Source::variant(day, variant),
+                        Fixture::params(YEAR, day, variant).map_err(|e| e.to_string())?,
+                    ),
+                    None => (Source::day(day), Params::new()),
+                };
+                let input = source.read().map_err(|e| e.to_string())?;
+                self.load(day, &input, &params)?;
+                return Ok(Reply::Output(format!("loaded day {}", day)));
+            }
+            _ => {}
+        }
+        let model = self
+            .model
+            .as_ref()
+            .ok_or("nothing loaded, try `load <day> [variant]`")?;
+        catch_unwind(AssertUnwindSafe(|| model.run(name, args)))
+            .map_err(|payload| format!("command panicked: {}", panic_message(&*payload)))?
+            .ok_or_else(|| format!("unknown command `{}`, try `help`", name))?
+            .map(Reply::Output)
+    }
+
+    fn help(&self) -> String {
+        let mut commands = vec![
+            ("load", "<day> [variant]", "parse a day's input"),
+            ("help", "", "list commands"),
+            ("quit", "", "leave"),
+        ];
+        if let Some(model) = &self.model {
+            commands.push(("part1", "", "answer to part 1"));
+            commands.push(("part2", "", "answer to part 2"));
+            commands.extend(model.help());
+        }
+        let width = commands
+            .iter()
+            .map(|(name, usage, _)| name.len() + usage.len() + 1)
+            .max()
+            .unwrap_or(0);
+        commands
+            .into_iter()
+            .map(|(name, usage, about)| {
+                let call = format!("{} {}", name, usage);
+                format!("{:width$}  {}", call, about, width = width)
+            })
+            .join("\n")
+    }
+}
+
+/// Reads commands from `input` until it ends or the user quits, writing
+/// replies to `out`. Prompts are only shown if `prompt` is set.
+pub fn repl(
+    session: &mut Session,
+    mut input: impl BufRead,
+    out: &mut impl Write,
+    prompt: bool,
+) -> io::Result<()> {
+    loop {
+        if prompt {
+            write!(out, "{}", session.prompt())?;
+            out.flush()?;
+        }
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        match session.execute(&line) {
+            Ok(Reply::Output(text)) if text.is_empty() => {}
+            Ok(Reply::Output(text)) => writeln!(out, "{}", text)?,
+            Ok(Reply::Quit) => return Ok(()),
+            Err(e) => writeln!(out, "error: {}", e)?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::{get_variant, Source};
+
+    use super::*;
+
+    fn session(day: usize) -> Session {
+        let mut session = Session::new();
+        let params = Fixture::params(YEAR, day, "small").unwrap();
+        session
+            .load(day, &get_variant(day, "small").unwrap(), &params)
+            .unwrap();
+        session
+    }
+
+    fn output(session: &mut Session, line: &str) -> String {
+        match session.execute(line) {
+            Ok(Reply::Output(text)) => text,
+            reply => panic!("{:?}", reply),
+        }
+    }
+
+    #[test]
+    fn reports() {
+        let mut session = session(2);
+        assert_eq!("2", output(&mut session, "part1"));
+        assert_eq!(
+            "report 4: 1 3 2 4 5 (without level 2)\nreport 5: 8 6 4 4 1 (without level 3)",
+            output(&mut session, "dampened")
+        );
+        assert_eq!(
+            "1 3 2 4 5 safe without level 2",
+            output(&mut session, "report 4")
+        );
+        assert_eq!(
+            Err("there is no report 7".to_string()),
+            session.execute("report 7")
+        );
+        assert_eq!(
+            Err("usage: report <n>".to_string()),
+            session.execute("report")
+        );
+    }
+
+    #[test]
+    fn program() {
+        let mut session = session(17);
+        assert_eq!("4,6,3,5,6,3,5,2,1,0", output(&mut session, "run"));
+        assert_eq!("4,2,1,0", output(&mut session, "run 8"));
+        assert!(output(&mut session, "disassemble").starts_with("rA /= 2**1\npush rA % 8"));
+        assert_eq!(
+            Err("`x` isn't a valid register value".to_string()),
+            session.execute("run x")
+        );
+    }
+
+    #[test]
+    fn memory_space() {
+        let mut session = session(18);
+        assert_eq!("22", output(&mut session, "part1"));
+        assert_eq!("6,1", output(&mut session, "part2"));
+        let input = Source::day(18).read().unwrap();
+        session.load(18, &input, &Params::new()).unwrap();
+        assert_eq!("326", output(&mut session, "part1"));
+        // A space of any size, with its bytes all inside the small example's
+        let params = Params::from([("width".to_string(), 9), ("bytes".to_string(), 0)]);
+        session
+            .load(18, &get_variant(18, "small").unwrap(), &params)
+            .unwrap();
+        assert_eq!("16", output(&mut session, "part1"));
+    }
+
+    #[test]
+    fn repl_session() {
+        let input = "help\n\nload 3\nload 18 small\nsteps 12\nfirst-byte\nwat\nquit\nsteps 12\n";
+        let mut session = Session::new();
+        let mut out = Vec::new();
+        repl(&mut session, input.as_bytes(), &mut out, false).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let lines = out.lines().collect_vec();
+        assert!(lines[0].starts_with("load <day> [variant]"));
+        assert_eq!(
+            &[
+                "error: day 3 can't be explored, try 2, 17, 18",
+                "loaded day 18",
+            ],
+            &lines[3..5]
+        );
+        assert_eq!(
+            "error: unknown command `wat`, try `help`",
+            lines[lines.len() - 1]
+        );
+        assert_eq!("day18> ", session.prompt());
+    }
+}
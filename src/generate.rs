@@ -0,0 +1,747 @@
+use std::{collections::HashSet, fmt::Write};
+
+use itertools::Itertools;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{
+    day17::Cpu,
+    geometry::{Direction, Point},
+    graph,
+    solution::DAYS,
+    utils::Grid,
+};
+
+/// Writes a random puzzle input for one day. `size` is the number of lines,
+/// items or map width the input should roughly have; formats with fixed
+/// dimensions clamp it to what the day accepts.
+pub type Generator = fn(&mut StdRng, usize) -> String;
+
+/// Generates an input for `day` that its parser accepts. The same seed and
+/// size always produce the same input.
+pub fn generate(day: usize, size: usize, seed: u64) -> Option<String> {
+    let generator = day.checked_sub(1).and_then(|i| GENERATORS.get(i))?;
+    Some(generator(&mut StdRng::seed_from_u64(seed), size.max(1)))
+}
+
+const GENERATORS: [Generator; DAYS] = [
+    location_lists,
+    reports,
+    corrupted_memory,
+    word_search,
+    print_queue,
+    guard_map,
+    equations,
+    antenna_map,
+    disk_map,
+    topographic_map,
+    stones,
+    garden,
+    claw_machines,
+    robots,
+    warehouse,
+    reindeer_maze,
+    program,
+    falling_bytes,
+    towels,
+    racetrack,
+    door_codes,
+    secrets,
+    network,
+    gates,
+    schematics,
+];
+
+fn render(rows: &[Vec<char>]) -> String {
+    rows.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// A maze of `#` walls with single-width corridors, every open tile reachable
+/// from every other. Cut from a randomised depth-first search over the tiles
+/// at odd coordinates, so it's always an odd number of tiles across.
+fn maze(rng: &mut StdRng, size: usize) -> Vec<Vec<char>> {
+    let cells = (size.max(5) - 1) / 2;
+    let width = cells * 2 + 1;
+    let mut rows = vec![vec!['#'; width]; width];
+    let tile = |p: Point| Point::new(p.y * 2 + 1, p.x * 2 + 1);
+    let mut stack = vec![Point::ORIGIN];
+    rows[1][1] = '.';
+    while let Some(&cell) = stack.last() {
+        let unvisited = cell
+            .neighbours()
+            .filter(|n| {
+                (0..cells as isize).contains(&n.y)
+                    && (0..cells as isize).contains(&n.x)
+                    && rows[tile(*n).y as usize][tile(*n).x as usize] == '#'
+            })
+            .collect_vec();
+        match unvisited.choose(rng) {
+            Some(&next) => {
+                let (a, b) = (tile(cell), tile(next));
+                rows[((a.y + b.y) / 2) as usize][((a.x + b.x) / 2) as usize] = '.';
+                rows[b.y as usize][b.x as usize] = '.';
+                stack.push(next);
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    rows
+}
+
+fn location_lists(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{}   {}\n",
+                rng.gen_range(10000..100000),
+                rng.gen_range(10000..100000)
+            )
+        })
+        .collect()
+}
+
+fn reports(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let sign = if rng.gen() { 1 } else { -1 };
+            let mut level: i64 = rng.gen_range(10..90);
+            let mut levels = vec![level];
+            for _ in 0..rng.gen_range(4..8) {
+                // Mostly safe steps, with the odd one too big or backwards
+                let step = match rng.gen_range(0..10) {
+                    0 => rng.gen_range(-3..=0),
+                    1 => rng.gen_range(4..=6),
+                    _ => rng.gen_range(1..=3),
+                };
+                level += step * sign;
+                levels.push(level);
+            }
+            levels.iter().join(" ") + "\n"
+        })
+        .collect()
+}
+
+fn corrupted_memory(rng: &mut StdRng, size: usize) -> String {
+    const JUNK: &[u8] = b"!@#$%^&*()[]{}<>,+-_?:;'who select from mul don't do";
+    let mut memory = String::new();
+    for _ in 0..size {
+        match rng.gen_range(0..10) {
+            0 => memory.push_str("do()"),
+            1 => memory.push_str("don't()"),
+            2 => write!(
+                memory,
+                "mul({},{}]",
+                rng.gen_range(1..1000),
+                rng.gen_range(1..1000)
+            )
+            .unwrap(),
+            3..=5 => write!(
+                memory,
+                "mul({},{})",
+                rng.gen_range(1..1000),
+                rng.gen_range(1..1000)
+            )
+            .unwrap(),
+            _ => {}
+        }
+        for _ in 0..rng.gen_range(0..8) {
+            memory.push(*JUNK.choose(rng).unwrap() as char);
+        }
+    }
+    memory + "\n"
+}
+
+fn word_search(rng: &mut StdRng, size: usize) -> String {
+    let rows = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| *b"XMAS".choose(rng).unwrap() as char)
+                .collect()
+        })
+        .collect_vec();
+    render(&rows)
+}
+
+fn print_queue(rng: &mut StdRng, size: usize) -> String {
+    // Every pair of pages is ordered, so any update can be put right
+    let mut pages = (10..100).collect_vec();
+    pages.shuffle(rng);
+    pages.truncate(size.clamp(3, pages.len()));
+    let mut out = String::new();
+    for (i, a) in pages.iter().enumerate() {
+        for b in &pages[i + 1..] {
+            writeln!(out, "{}|{}", a, b).unwrap();
+        }
+    }
+    out.push('\n');
+    for _ in 0..size {
+        // Updates have a middle page, so an odd number of them
+        let length = rng.gen_range(1..=(pages.len() - 1) / 2) * 2 + 1;
+        let mut update = pages.choose_multiple(rng, length).collect_vec();
+        if rng.gen() {
+            update.sort_by_key(|page| pages.iter().position(|p| p == *page));
+        }
+        writeln!(out, "{}", update.iter().join(",")).unwrap();
+    }
+    out
+}
+
+fn guard_map(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(2);
+    // The solution follows the guard until they leave, so retry until they do
+    loop {
+        let mut rows = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.gen_ratio(1, 12) { '#' } else { '.' })
+                    .collect_vec()
+            })
+            .collect_vec();
+        let start = Point::new(
+            rng.gen_range(0..size) as isize,
+            rng.gen_range(0..size) as isize,
+        );
+        rows[start.y as usize][start.x as usize] = '^';
+        if guard_leaves(&rows, start) {
+            return render(&rows);
+        }
+    }
+}
+
+fn guard_leaves(rows: &[Vec<char>], start: Point) -> bool {
+    let size = rows.len() as isize;
+    let inside = |p: Point| (0..size).contains(&p.y) && (0..size).contains(&p.x);
+    let mut seen = HashSet::new();
+    let (mut position, mut direction) = (start, Direction::Up);
+    while inside(position) {
+        if !seen.insert((position, direction)) {
+            return false;
+        }
+        let facing = position + direction;
+        if inside(facing) && rows[facing.y as usize][facing.x as usize] == '#' {
+            direction = direction.clockwise();
+        } else {
+            position = facing;
+        }
+    }
+    true
+}
+
+fn equations(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            // At most 8 two digit numbers, so even concatenating them all
+            // fits in an i64
+            let values = (0..rng.gen_range(2..=8))
+                .map(|_| rng.gen_range(1..100_i64))
+                .collect_vec();
+            let mut target = values[0];
+            for value in &values[1..] {
+                target = match rng.gen_range(0..3) {
+                    0 => target + value,
+                    1 => target * value,
+                    _ => format!("{}{}", target, value).parse().unwrap(),
+                };
+            }
+            // Some equations can't be made true
+            if rng.gen_ratio(1, 3) {
+                target += rng.gen_range(1..10);
+            }
+            format!("{}: {}\n", target, values.iter().join(" "))
+        })
+        .collect()
+}
+
+fn antenna_map(rng: &mut StdRng, size: usize) -> String {
+    const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let frequencies = FREQUENCIES
+        .choose_multiple(rng, size.div_ceil(5).min(FREQUENCIES.len()))
+        .copied()
+        .collect_vec();
+    let rows = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| match rng.gen_ratio(1, 20) {
+                    true => *frequencies.choose(rng).unwrap() as char,
+                    false => '.',
+                })
+                .collect()
+        })
+        .collect_vec();
+    render(&rows)
+}
+
+fn disk_map(rng: &mut StdRng, size: usize) -> String {
+    // Files take at least a block and the map ends on one, like the full input
+    (0..size | 1)
+        .map(|i| {
+            let blocks = if i % 2 == 0 { 1..10 } else { 0..10 };
+            char::from_digit(rng.gen_range(blocks), 10).unwrap()
+        })
+        .collect::<String>()
+        + "\n"
+}
+
+fn topographic_map(rng: &mut StdRng, size: usize) -> String {
+    let mut rows = (0..size)
+        .map(|_| (0..size).map(|_| rng.gen_range(0..10)).collect_vec())
+        .collect_vec();
+    // Lay trails climbing from 0 to 9 over the noise
+    for _ in 0..size {
+        let mut p = Point::new(
+            rng.gen_range(0..size) as isize,
+            rng.gen_range(0..size) as isize,
+        );
+        for height in 0..10 {
+            rows[p.y as usize][p.x as usize] = height;
+            let next = p
+                .neighbours()
+                .filter(|n| (0..size as isize).contains(&n.y) && (0..size as isize).contains(&n.x))
+                .collect_vec();
+            match next.choose(rng) {
+                Some(n) => p = *n,
+                None => break,
+            }
+        }
+    }
+    let rows = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|h| char::from_digit(*h, 10).unwrap())
+                .collect()
+        })
+        .collect_vec();
+    render(&rows)
+}
+
+fn stones(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| match rng.gen_range(0..3) {
+            0 => rng.gen_range(0..10),
+            1 => rng.gen_range(10..10000),
+            _ => rng.gen_range(10000..10000000),
+        })
+        .join(" ")
+        + "\n"
+}
+
+fn garden(rng: &mut StdRng, size: usize) -> String {
+    // Copying a neighbour's plant most of the time grows regions
+    let mut rows = vec![vec!['A'; size]; size];
+    for y in 0..size {
+        for x in 0..size {
+            rows[y][x] = match (rng.gen_range(0..10), y, x) {
+                (0..=4, _, 1..) => rows[y][x - 1],
+                (5..=8, 1.., _) => rows[y - 1][x],
+                _ => rng.gen_range('A'..='Z'),
+            };
+        }
+    }
+    render(&rows)
+}
+
+fn claw_machines(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let a = (rng.gen_range(10..100), rng.gen_range(10..100));
+            // Buttons moving in the same direction leave no unique answer
+            let b = loop {
+                let b = (rng.gen_range(10..100), rng.gen_range(10..100));
+                if a.0 * b.1 != a.1 * b.0 {
+                    break b;
+                }
+            };
+            let (i, j) = (rng.gen_range(0..100), rng.gen_range(0..100));
+            let mut prize = (a.0 * i + b.0 * j, a.1 * i + b.1 * j);
+            // Some prizes can't be won
+            if rng.gen() {
+                prize.0 += rng.gen_range(1..100);
+            }
+            format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+                a.0, a.1, b.0, b.1, prize.0, prize.1
+            )
+        })
+        .join("\n")
+}
+
+/// Robots for the 101 by 103 bathroom of the full input. They all stand on
+/// different tiles at some point, which is when the solution sees a tree.
+fn robots(rng: &mut StdRng, size: usize) -> String {
+    let (width, height) = (101_i64, 103_i64);
+    let seconds = rng.gen_range(0..width * height);
+    let mut tiles = (0..height).cartesian_product(0..width).collect_vec();
+    tiles.shuffle(rng);
+    tiles
+        .into_iter()
+        .take(size)
+        .map(|(y, x)| {
+            let v = (rng.gen_range(-100..=100), rng.gen_range(-100..=100));
+            // Run the robot backwards from where it is after `seconds`
+            let p = (
+                (x - v.0 * seconds).rem_euclid(width),
+                (y - v.1 * seconds).rem_euclid(height),
+            );
+            format!("p={},{} v={},{}\n", p.0, p.1, v.0, v.1)
+        })
+        .collect()
+}
+
+fn warehouse(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(3);
+    let mut rows = (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| match rng.gen_range(0..10) {
+                    _ if y == 0 || x == 0 || y == size - 1 || x == size - 1 => '#',
+                    0 => '#',
+                    1..=3 => 'O',
+                    _ => '.',
+                })
+                .collect_vec()
+        })
+        .collect_vec();
+    rows[rng.gen_range(1..size - 1)][rng.gen_range(1..size - 1)] = '@';
+    let moves = (0..size * size)
+        .map(|_| *b"^v<>".choose(rng).unwrap() as char)
+        .chunks(70)
+        .into_iter()
+        .map(|line| line.collect::<String>() + "\n")
+        .collect::<String>();
+    render(&rows) + "\n" + &moves
+}
+
+fn reindeer_maze(rng: &mut StdRng, size: usize) -> String {
+    let mut rows = maze(rng, size);
+    let width = rows.len();
+    // Knock out some walls so there's more than one way through
+    for _ in 0..width {
+        let (y, x) = (rng.gen_range(1..width - 1), rng.gen_range(1..width - 1));
+        if (y + x) % 2 == 1 {
+            rows[y][x] = '.';
+        }
+    }
+    rows[width - 2][1] = 'S';
+    rows[1][width - 2] = 'E';
+    render(&rows)
+}
+
+/// A program in the shape of the full input: it mixes the low bits of A with
+/// two constants, outputs a value, and shifts A along until it's zero. Only
+/// constants that let some value of A make the program print itself are used,
+/// and none are 7, which the CPU rejects as a combo operand.
+fn program(rng: &mut StdRng, size: usize) -> String {
+    let a = rng.gen_range(0..8_u64.saturating_pow(size.min(16) as u32));
+    loop {
+        let program = [
+            2,
+            4,
+            1,
+            rng.gen_range(0..7),
+            7,
+            5,
+            1,
+            rng.gen_range(0..7),
+            4,
+            rng.gen_range(0..7),
+            5,
+            5,
+            0,
+            3,
+            3,
+            0,
+        ];
+        let input = format!(
+            "Register A: {}\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n",
+            a,
+            program.iter().join(",")
+        );
+        let cpu = Cpu::new(&input).unwrap();
+        // Build A three bits at a time, matching the program from its end
+        let mut ras = vec![0];
+        for i in 0..program.len() {
+            ras = ras
+                .into_iter()
+                .flat_map(|ra| (0..8).map(move |end| (ra << 3) + end))
+                .filter(|ra| cpu.run_with_ra(*ra) == program[program.len() - 1 - i..])
+                .collect();
+        }
+        if !ras.is_empty() {
+            return input;
+        }
+    }
+}
+
+/// Bytes falling into the 71 by 71 memory space of the full input. The exit
+/// is still reachable after the first 1024, which the solution simulates, and
+/// cut off by a later one.
+fn falling_bytes(rng: &mut StdRng, size: usize) -> String {
+    let (start, exit) = (Point::ORIGIN, Point::new(70, 70));
+    let mut points = (0..71)
+        .cartesian_product(0..71)
+        .map(|(y, x)| Point::new(y, x))
+        .filter(|p| *p != start && *p != exit)
+        .collect_vec();
+    loop {
+        points.shuffle(rng);
+        let reachable = |count: usize| {
+            let mut grid = Grid::new(71, 71, true);
+            for p in &points[..count] {
+                grid[*p] = false;
+            }
+            let neighbours = |p: &Point| {
+                grid.neighbours(*p)
+                    .filter(|n| grid[*n])
+                    .map(|n| (n, 1))
+                    .collect_vec()
+            };
+            graph::bfs(&neighbours, [start]).distance(&exit).is_some()
+        };
+        if !reachable(1024) {
+            continue;
+        }
+        // The first byte that cuts off the exit, found by bisection
+        let (mut low, mut high) = (1024, points.len());
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            if reachable(mid) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        // `size` bytes from the one that cuts off the exit onwards
+        return points[..(high - 1 + size).min(points.len())]
+            .iter()
+            .map(|p| format!("{},{}\n", p.x, p.y))
+            .collect();
+    }
+}
+
+fn towels(rng: &mut StdRng, size: usize) -> String {
+    const COLOURS: &[u8] = b"wubrg";
+    let stripes = |rng: &mut StdRng, length| {
+        (0..length)
+            .map(|_| *COLOURS.choose(rng).unwrap() as char)
+            .collect::<String>()
+    };
+    let patterns = (0..size)
+        .map(|_| {
+            let length = rng.gen_range(1..=8);
+            stripes(rng, length)
+        })
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .sorted()
+        .collect_vec();
+    let designs = (0..size)
+        .map(|_| {
+            let length = rng.gen_range(20..=60);
+            let mut design = String::new();
+            while design.len() < length {
+                // Mostly made of towels, with the odd stripe that may not be
+                if rng.gen_ratio(1, 20) {
+                    design.push_str(&stripes(rng, 1));
+                } else {
+                    design.push_str(patterns.choose(rng).unwrap());
+                }
+            }
+            design + "\n"
+        })
+        .collect::<String>();
+    patterns.join(", ") + "\n\n" + &designs
+}
+
+/// A single track with no branches: the path from `S` to `E` through a maze,
+/// with the rest of the maze walled off.
+fn racetrack(rng: &mut StdRng, size: usize) -> String {
+    let maze = maze(rng, size);
+    let width = maze.len();
+    let start = Point::new(width as isize - 2, 1);
+    let end = Point::new(1, width as isize - 2);
+    let open = |p: Point| maze[p.y as usize][p.x as usize] != '#';
+    // The maze is a tree, so a depth-first search finds the only path
+    let mut path = vec![start];
+    let mut tried = HashSet::from([start]);
+    while *path.last().unwrap() != end {
+        let current = *path.last().unwrap();
+        match current
+            .neighbours()
+            .find(|n| open(*n) && !tried.contains(n))
+        {
+            Some(next) => {
+                tried.insert(next);
+                path.push(next);
+            }
+            None => {
+                path.pop();
+            }
+        }
+    }
+    let mut rows = vec![vec!['#'; width]; width];
+    for p in &path {
+        rows[p.y as usize][p.x as usize] = '.';
+    }
+    rows[start.y as usize][start.x as usize] = 'S';
+    rows[end.y as usize][end.x as usize] = 'E';
+    render(&rows)
+}
+
+fn door_codes(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{:03}A\n", rng.gen_range(1..1000)))
+        .collect()
+}
+
+fn secrets(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| format!("{}\n", rng.gen_range(1..16777216)))
+        .collect()
+}
+
+fn network(rng: &mut StdRng, size: usize) -> String {
+    let mut names = (b'a'..=b'z')
+        .cartesian_product(b'a'..=b'z')
+        .map(|(a, b)| format!("{}{}", a as char, b as char))
+        .collect_vec();
+    names.shuffle(rng);
+    names.truncate(size.clamp(2, names.len()));
+    let mut edges = HashSet::new();
+    for i in 0..names.len() {
+        for _ in 0..4 {
+            let j = rng.gen_range(0..names.len());
+            if i != j && !edges.contains(&(j, i)) {
+                edges.insert((i, j));
+            }
+        }
+    }
+    edges
+        .into_iter()
+        .sorted()
+        .map(|(i, j)| format!("{}-{}\n", names[i], names[j]))
+        .collect()
+}
+
+/// A ripple carry adder of `size` bits, with the gates shuffled and given
+/// random names like the full input.
+fn gates(rng: &mut StdRng, size: usize) -> String {
+    let bits = size.clamp(2, 63);
+    let mut names = HashSet::new();
+    let mut wire = |rng: &mut StdRng| loop {
+        let name = (0..3).map(|_| rng.gen_range('a'..='w')).collect::<String>();
+        if names.insert(name.clone()) {
+            break name;
+        }
+    };
+
+    let mut gates = Vec::new();
+    let mut carry: Option<String> = None;
+    for bit in 0..bits {
+        let (x, y, z) = (
+            format!("x{:02}", bit),
+            format!("y{:02}", bit),
+            format!("z{:02}", bit),
+        );
+        let next_carry = if bit == bits - 1 {
+            format!("z{:02}", bits)
+        } else {
+            wire(rng)
+        };
+        match carry {
+            None => {
+                gates.push((x.clone(), "XOR", y.clone(), z));
+                gates.push((x, "AND", y, next_carry.clone()));
+            }
+            Some(carry) => {
+                let (sum, both, through) = (wire(rng), wire(rng), wire(rng));
+                gates.push((x.clone(), "XOR", y.clone(), sum.clone()));
+                gates.push((x, "AND", y, both.clone()));
+                gates.push((sum.clone(), "XOR", carry.clone(), z));
+                gates.push((sum, "AND", carry, through.clone()));
+                gates.push((both, "OR", through, next_carry.clone()));
+            }
+        }
+        carry = Some(next_carry);
+    }
+    gates.shuffle(rng);
+
+    let mut out = String::new();
+    for prefix in ["x", "y"] {
+        for bit in 0..bits {
+            writeln!(out, "{}{:02}: {}", prefix, bit, rng.gen_range(0..2)).unwrap();
+        }
+    }
+    out.push('\n');
+    for (left, op, right, output) in gates {
+        let (left, right) = if rng.gen() {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        writeln!(out, "{} {} {} -> {}", left, op, right, output).unwrap();
+    }
+    out
+}
+
+/// Locks and keys are 5 pins wide with heights of 0 to 5.
+fn schematics(rng: &mut StdRng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let lock = rng.gen::<bool>();
+            let heights = (0..5).map(|_| rng.gen_range(0..=5)).collect_vec();
+            (0..7)
+                .map(|row| {
+                    let line = heights
+                        .iter()
+                        .map(|h| {
+                            // Locks fill down from the top, keys up from the bottom
+                            let filled = if lock { row <= *h } else { 6 - row <= *h };
+                            if filled {
+                                '#'
+                            } else {
+                                '.'
+                            }
+                        })
+                        .collect::<String>();
+                    line + "\n"
+                })
+                .collect::<String>()
+        })
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::{solution, Params};
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for day in 1..=DAYS {
+            let parse = solution(day).unwrap();
+            for (seed, size) in [(0, 1), (1, 5), (2, 20), (3, 50)] {
+                let input = generate(day, size, seed).unwrap();
+                assert!(
+                    parse(&input, &Params::new()).is_ok(),
+                    "day {} seed {} size {} didn't parse:\n{}",
+                    day,
+                    seed,
+                    size,
+                    input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn seeded() {
+        assert_eq!(generate(6, 10, 7), generate(6, 10, 7));
+        assert_ne!(generate(6, 10, 7), generate(6, 10, 8));
+        assert!(generate(26, 10, 0).is_none());
+    }
+}
@@ -0,0 +1,224 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position or offset on a map. `y` grows downwards, matching the row order
+/// of the input, and comes first like it does when reading a map.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub y: isize,
+    pub x: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    /// Offsets to all eight neighbours of a point, clockwise from up.
+    pub const SURROUNDING: [Point; 8] = [
+        Point::new(-1, 0),
+        Point::new(-1, 1),
+        Point::new(0, 1),
+        Point::new(1, 1),
+        Point::new(1, 0),
+        Point::new(1, -1),
+        Point::new(0, -1),
+        Point::new(-1, -1),
+    ];
+
+    pub const fn new(y: isize, x: isize) -> Self {
+        Self { y, x }
+    }
+
+    pub fn manhattan(&self, other: Point) -> usize {
+        self.y.abs_diff(other.y) + self.x.abs_diff(other.x)
+    }
+
+    /// The four orthogonal neighbours, clockwise from up.
+    pub fn neighbours(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self + d)
+    }
+
+    /// All eight neighbours, diagonals included, clockwise from up.
+    pub fn surrounding(self) -> impl Iterator<Item = Point> {
+        Self::SURROUNDING
+            .into_iter()
+            .map(move |offset| self + offset)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.y + rhs.y, self.x + rhs.x)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction) -> Point {
+        self + rhs.delta()
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.y - rhs.y, self.x - rhs.x)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: isize) -> Point {
+        Point::new(self.y * rhs, self.x * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.y, -self.x)
+    }
+}
+
+/// One of the four orthogonal directions on a map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Parses an arrow (`^`, `>`, `v`, `<`) or compass point (`N`, `E`, `S`,
+    /// `W`).
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '^' | 'N' => Some(Self::Up),
+            '>' | 'E' => Some(Self::Right),
+            'v' | 'S' => Some(Self::Down),
+            '<' | 'W' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    /// The arrow for this direction.
+    pub fn arrow(&self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        }
+    }
+
+    /// The offset of one step in this direction.
+    pub fn delta(&self) -> Point {
+        match self {
+            Self::Up => Point::new(-1, 0),
+            Self::Right => Point::new(0, 1),
+            Self::Down => Point::new(1, 0),
+            Self::Left => Point::new(0, -1),
+        }
+    }
+
+    pub fn clockwise(&self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    pub fn counterclockwise(&self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Left => Self::Down,
+            Self::Down => Self::Right,
+            Self::Right => Self::Up,
+        }
+    }
+
+    pub fn opposite(&self) -> Self {
+        self.clockwise().clockwise()
+    }
+
+    /// Whether this moves along the `x` axis.
+    pub fn horizontal(&self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(Point::new(5, 0), a + b);
+        assert_eq!(Point::new(-3, 4), a - b);
+        assert_eq!(Point::new(3, 6), a * 3);
+        assert_eq!(Point::new(-1, -2), -a);
+        assert_eq!(7, a.manhattan(b));
+        assert_eq!(Point::new(0, 2), a + Direction::Up);
+    }
+
+    #[test]
+    fn neighbours() {
+        let p = Point::new(0, 0);
+        assert_eq!(
+            vec![
+                Point::new(-1, 0),
+                Point::new(0, 1),
+                Point::new(1, 0),
+                Point::new(0, -1)
+            ],
+            p.neighbours().collect::<Vec<_>>()
+        );
+        assert_eq!(8, p.surrounding().count());
+    }
+
+    #[test]
+    fn directions() {
+        for d in Direction::ALL {
+            assert_eq!(Some(d), Direction::from_char(d.arrow()));
+            assert_eq!(d, d.clockwise().counterclockwise());
+            assert_eq!(-d.delta(), d.opposite().delta());
+        }
+        assert_eq!(Some(Direction::Left), Direction::from_char('W'));
+        assert_eq!(None, Direction::from_char('x'));
+    }
+}
@@ -0,0 +1,302 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use tracing::instrument;
+
+/// A graph described by a function from a node to its neighbours, each with
+/// the cost of the edge leading to it. Any closure
+/// `Fn(&N) -> impl IntoIterator<Item = (N, u32)>` is a graph, so days can
+/// build neighbours on the fly from their own maps.
+pub trait Graph<N> {
+    fn neighbours(&self, node: &N) -> impl IntoIterator<Item = (N, u32)>;
+}
+
+impl<N, F, I> Graph<N> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, u32)>,
+{
+    fn neighbours(&self, node: &N) -> impl IntoIterator<Item = (N, u32)> {
+        self(node)
+    }
+}
+
+/// Shortest distances from the start nodes of a search, along with every
+/// predecessor a node can be reached through at that distance. Together the
+/// predecessors form a DAG of all shortest paths.
+#[derive(Clone, Debug)]
+pub struct Paths<N> {
+    distances: HashMap<N, u32>,
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N: Clone + Eq + Hash> Paths<N> {
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<u32> {
+        self.distances.get(node).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<N, u32> {
+        &self.distances
+    }
+
+    pub fn into_distances(self) -> HashMap<N, u32> {
+        self.distances
+    }
+
+    pub fn predecessors(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// One shortest path from a start node to `node`, both ends included.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        self.distances.get(node)?;
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors(path.last().unwrap()).first() {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Every node on any shortest path to one of `targets`, targets included.
+    pub fn on_shortest_paths(&self, targets: impl IntoIterator<Item = N>) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        let mut stack = targets
+            .into_iter()
+            .filter(|target| self.distances.contains_key(target))
+            .collect::<Vec<_>>();
+        while let Some(node) = stack.pop() {
+            if seen.insert(node.clone()) {
+                stack.extend(self.predecessors(&node).iter().cloned());
+            }
+        }
+        seen
+    }
+
+    /// Records reaching `node` from `from` at `distance`, returning whether
+    /// it's a new shortest distance.
+    fn relax(&mut self, node: &N, from: &N, distance: u32) -> bool {
+        match self.distances.get(node) {
+            Some(d) if *d < distance => false,
+            Some(d) if *d == distance => {
+                self.predecessors
+                    .entry(node.clone())
+                    .or_default()
+                    .push(from.clone());
+                false
+            }
+            _ => {
+                self.distances.insert(node.clone(), distance);
+                self.predecessors.insert(node.clone(), vec![from.clone()]);
+                true
+            }
+        }
+    }
+}
+
+/// Shortest paths from any of `starts` to every reachable node.
+#[instrument(skip_all)]
+pub fn dijkstra<N, G>(graph: &G, starts: impl IntoIterator<Item = N>) -> Paths<N>
+where
+    N: Clone + Eq + Hash + Ord,
+    G: Graph<N>,
+{
+    let mut paths = Paths::new();
+    let mut settled = HashSet::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        paths.distances.insert(start.clone(), 0);
+        queue.push(Reverse((0, start)));
+    }
+
+    while let Some(Reverse((distance, node))) = queue.pop() {
+        if !settled.insert(node.clone()) {
+            continue;
+        }
+        for (next, cost) in graph.neighbours(&node) {
+            let next_distance = distance + cost;
+            if paths.relax(&next, &node, next_distance) {
+                queue.push(Reverse((next_distance, next)));
+            }
+        }
+    }
+    paths
+}
+
+/// Shortest paths counted in steps, ignoring edge costs.
+#[instrument(skip_all)]
+pub fn bfs<N, G>(graph: &G, starts: impl IntoIterator<Item = N>) -> Paths<N>
+where
+    N: Clone + Eq + Hash,
+    G: Graph<N>,
+{
+    let mut paths = Paths::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        paths.distances.insert(start.clone(), 0);
+        queue.push_back(start);
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let distance = paths.distances[&node] + 1;
+        for (next, _) in graph.neighbours(&node) {
+            if paths.relax(&next, &node, distance) {
+                queue.push_back(next);
+            }
+        }
+    }
+    paths
+}
+
+/// The cost of a cheapest path from `start` to a node satisfying `goal`, and
+/// the path itself. `heuristic` must never overestimate the remaining cost.
+#[instrument(skip_all)]
+pub fn astar<N, G>(
+    graph: &G,
+    start: N,
+    goal: impl Fn(&N) -> bool,
+    heuristic: impl Fn(&N) -> u32,
+) -> Option<(u32, Vec<N>)>
+where
+    N: Clone + Eq + Hash + Ord,
+    G: Graph<N>,
+{
+    let mut paths = Paths::new();
+    let mut queue = BinaryHeap::new();
+    paths.distances.insert(start.clone(), 0);
+    queue.push(Reverse((heuristic(&start), 0, start)));
+
+    while let Some(Reverse((_, distance, node))) = queue.pop() {
+        if goal(&node) {
+            let path = paths.path(&node).unwrap();
+            return Some((distance, path));
+        }
+        if paths.distances[&node] < distance {
+            continue;
+        }
+        for (next, cost) in graph.neighbours(&node) {
+            let next_distance = distance + cost;
+            if paths.relax(&next, &node, next_distance) {
+                queue.push(Reverse((
+                    next_distance + heuristic(&next),
+                    next_distance,
+                    next,
+                )));
+            }
+        }
+    }
+    None
+}
+
+/// Every maximal clique of an undirected graph, found with Bron–Kerbosch
+/// using pivots. `adjacency` must list each edge in both directions.
+#[instrument(skip_all)]
+pub fn maximal_cliques<N>(adjacency: &HashMap<N, HashSet<N>>) -> Vec<HashSet<N>>
+where
+    N: Clone + Eq + Hash,
+{
+    let mut cliques = Vec::new();
+    bron_kerbosch(
+        adjacency,
+        &mut cliques,
+        HashSet::new(),
+        adjacency.keys().cloned().collect(),
+        HashSet::new(),
+    );
+    cliques
+}
+
+fn bron_kerbosch<N>(
+    adjacency: &HashMap<N, HashSet<N>>,
+    cliques: &mut Vec<HashSet<N>>,
+    r: HashSet<N>,
+    mut p: HashSet<N>,
+    mut x: HashSet<N>,
+) where
+    N: Clone + Eq + Hash,
+{
+    let Some(u) = p.union(&x).next().cloned() else {
+        cliques.push(r);
+        return;
+    };
+    let candidates = p.difference(&adjacency[&u]).cloned().collect::<Vec<_>>();
+    for v in candidates {
+        let neighbours = &adjacency[&v];
+        let mut r = r.clone();
+        r.insert(v.clone());
+        bron_kerbosch(
+            adjacency,
+            cliques,
+            r,
+            p.intersection(neighbours).cloned().collect(),
+            x.intersection(neighbours).cloned().collect(),
+        );
+        p.remove(&v);
+        x.insert(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -1- 1 -1- 3
+    //  \         /
+    //   2 --5-- +
+    fn graph(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            0 => vec![(1, 1), (2, 1)],
+            1 => vec![(3, 1)],
+            2 => vec![(3, 5)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn shortest_paths() {
+        let paths = dijkstra(&graph, [0]);
+        assert_eq!(Some(2), paths.distance(&3));
+        assert_eq!(Some(vec![0, 1, 3]), paths.path(&3));
+        assert_eq!(HashSet::from([0, 1, 3]), paths.on_shortest_paths([3]));
+
+        let paths = bfs(&graph, [0]);
+        assert_eq!(Some(2), paths.distance(&3));
+        assert_eq!(&[1, 2], paths.predecessors(&3));
+        assert_eq!(HashSet::from([0, 1, 2, 3]), paths.on_shortest_paths([3]));
+
+        assert_eq!(
+            Some((2, vec![0, 1, 3])),
+            astar(&graph, 0, |n| *n == 3, |_| 0)
+        );
+        assert_eq!(None, astar(&graph, 1, |n| *n == 2, |_| 0));
+    }
+
+    #[test]
+    fn cliques() {
+        let mut adjacency = HashMap::<_, HashSet<_>>::new();
+        for (a, b) in [(1, 2), (2, 3), (1, 3), (3, 4)] {
+            adjacency.entry(a).or_default().insert(b);
+            adjacency.entry(b).or_default().insert(a);
+        }
+        let mut cliques = maximal_cliques(&adjacency)
+            .into_iter()
+            .map(|clique| {
+                let mut clique = clique.into_iter().collect::<Vec<_>>();
+                clique.sort();
+                clique
+            })
+            .collect::<Vec<_>>();
+        cliques.sort();
+        assert_eq!(vec![vec![1, 2, 3], vec![3, 4]], cliques);
+    }
+}
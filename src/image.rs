@@ -0,0 +1,195 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs,
+    hash::{Hash, Hasher},
+    io,
+    path::Path,
+};
+
+use crate::{
+    day12, day14, day16, day20, error::Result, geometry::Point, solution::Solution, utils::Grid,
+};
+
+pub type Rgb = [u8; 3];
+
+pub const BACKGROUND: Rgb = [16, 16, 24];
+pub const WALL: Rgb = [64, 64, 72];
+pub const FLOOR: Rgb = [200, 200, 200];
+pub const PATH: Rgb = [255, 160, 0];
+pub const START: Rgb = [0, 200, 80];
+pub const END: Rgb = [220, 40, 40];
+pub const ROBOT: Rgb = [120, 255, 120];
+
+/// A picture of a map, drawn with each cell as a square of `scale` pixels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    scale: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// Draws every cell of `grid` in the colour `palette` gives it.
+    pub fn from_grid<T>(grid: &Grid<T>, scale: usize, palette: impl Fn(&T) -> Rgb) -> Self {
+        let scale = scale.max(1);
+        let mut image = Self {
+            width: grid.width() * scale,
+            height: grid.height() * scale,
+            scale,
+            pixels: vec![BACKGROUND; grid.width() * grid.height() * scale * scale],
+        };
+        for (p, cell) in grid.iter() {
+            image.paint(p, palette(cell));
+        }
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The colour of the pixel at `(x, y)`.
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Fills the cell at `p`, ignoring cells outside the image.
+    pub fn paint(&mut self, p: Point, colour: Rgb) {
+        // Checked before scaling, which would wrap negative coordinates
+        let scaled = |v: isize, size: usize| {
+            usize::try_from(v)
+                .ok()
+                .and_then(|v| v.checked_mul(self.scale))
+                .filter(|v| *v < size)
+        };
+        let (Some(y), Some(x)) = (scaled(p.y, self.height), scaled(p.x, self.width)) else {
+            return;
+        };
+        for row in y..y + self.scale {
+            let start = row * self.width + x;
+            self.pixels[start..start + self.scale].fill(colour);
+        }
+    }
+
+    /// Fills every cell in `points`, drawn over what's already there.
+    pub fn overlay(&mut self, points: impl IntoIterator<Item = Point>, colour: Rgb) {
+        for p in points {
+            self.paint(p, colour);
+        }
+    }
+
+    /// The image as a binary PPM (P6).
+    pub fn ppm(&self) -> Vec<u8> {
+        let mut data = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        data.extend(self.pixels.iter().flatten());
+        data
+    }
+
+    pub fn png(&self) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.pixels.concat())
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)?;
+        Ok(data)
+    }
+
+    /// Writes the image as a PNG, or as a PPM if `path` ends in `.ppm`.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let data = match path.extension().and_then(|e| e.to_str()) {
+            Some("ppm") => self.ppm(),
+            _ => self.png()?,
+        };
+        fs::write(path, data)
+    }
+}
+
+/// A bright colour picked from a hash of `key`, so the same key always gets
+/// the same colour.
+pub fn hashed_colour(key: impl Hash) -> Rgb {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    let [r, g, b, ..] = hasher.finish().to_le_bytes();
+    // Keep away from the dark background
+    [r | 0x40, g | 0x40, b | 0x40]
+}
+
+/// A day whose solution can be drawn as a picture.
+pub trait Picture: Solution {
+    fn picture(&self, scale: usize) -> Image;
+}
+
+type Painter = fn(&str, usize) -> Result<Image>;
+
+fn painter<P: Picture>(input: &str, scale: usize) -> Result<Image> {
+    Ok(P::parse(input)?.picture(scale))
+}
+
+const PICTURES: [(usize, Painter); 4] = [
+    (12, painter::<day12::Garden>),
+    (14, painter::<day14::Bathroom>),
+    (16, painter::<day16::ReindeerMaze>),
+    (20, painter::<day20::Racetrack>),
+];
+
+/// Days that can be drawn.
+pub fn days() -> impl Iterator<Item = usize> {
+    PICTURES.iter().map(|(day, _)| *day)
+}
+
+/// Parses `input` for `day` and draws it, or `None` if the day can't be
+/// drawn.
+pub fn picture(day: usize, input: &str, scale: usize) -> Option<Result<Image>> {
+    let (_, painter) = PICTURES.iter().find(|(d, _)| *d == day)?;
+    Some(painter(input, scale))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::get_variant;
+
+    use super::*;
+
+    #[test]
+    fn draw() {
+        let grid = Grid::parse("#.\n..\n", "`#` or `.`", Some).unwrap();
+        let mut image = Image::from_grid(&grid, 2, |c| if *c == '#' { WALL } else { FLOOR });
+        image.overlay(
+            [Point::new(1, 1), Point::new(5, 5), Point::new(-1, 0)],
+            PATH,
+        );
+        image.paint(Point::new(0, isize::MIN), PATH);
+        assert_eq!((4, 4), (image.width(), image.height()));
+        assert_eq!(Some(WALL), image.pixel(1, 1));
+        assert_eq!(Some(FLOOR), image.pixel(2, 1));
+        assert_eq!(Some(PATH), image.pixel(3, 3));
+        assert_eq!(None, image.pixel(4, 0));
+
+        let ppm = image.ppm();
+        assert!(ppm.starts_with(b"P6\n4 4\n255\n"));
+        assert_eq!(11 + 4 * 4 * 3, ppm.len());
+        assert!(image.png().unwrap().starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn best_path() {
+        let input = get_variant(16, "small").unwrap();
+        let image = picture(16, &input, 1).unwrap().unwrap();
+        let path = (0..image.height())
+            .flat_map(|y| (0..image.width()).map(move |x| (x, y)))
+            .filter(|(x, y)| image.pixel(*x, *y) == Some(PATH))
+            .count();
+        // Every best path tile except the start and end
+        assert_eq!(43, path);
+        assert!(picture(1, &input, 1).is_none());
+    }
+}
@@ -0,0 +1,163 @@
+use std::{
+    env, fmt,
+    fs::File,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::{
+    error::{Error, Result},
+    solution::YEAR,
+};
+
+/// Environment variable that points the resolver at a different inputs
+/// directory.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    /// `dayNN.txt` in the inputs directory, or a named variant of it such as
+    /// `dayNN_small.txt`.
+    Day {
+        day: usize,
+        variant: Option<String>,
+    },
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    pub fn day(day: usize) -> Self {
+        Self::Day { day, variant: None }
+    }
+
+    pub fn variant(day: usize, name: &str) -> Self {
+        Self::Day {
+            day,
+            variant: Some(name.to_owned()),
+        }
+    }
+
+    /// A day's input, or a named variant of it, for any event. Events other
+    /// than [`YEAR`] keep theirs in a subdirectory named after the year.
+    pub fn in_year(year: u16, day: usize, variant: Option<&str>) -> Self {
+        match year {
+            YEAR => Self::Day {
+                day,
+                variant: variant.map(str::to_owned),
+            },
+            _ => Self::File(year_dir(year).join(file_name(day, variant))),
+        }
+    }
+
+    /// `-` reads standard input, anything else is a file path.
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            Self::Stdin
+        } else {
+            Self::File(arg.into())
+        }
+    }
+
+    pub fn path(&self) -> Option<PathBuf> {
+        match self {
+            Self::Day { day, variant } => Some(dir().join(file_name(*day, variant.as_deref()))),
+            Self::File(path) => Some(path.clone()),
+            Self::Stdin => None,
+        }
+    }
+
+    pub fn read(&self) -> Result<String> {
+        let mut input = String::new();
+        match self.path() {
+            Some(path) => File::open(path).and_then(|mut file| file.read_to_string(&mut input)),
+            None => io::stdin().read_to_string(&mut input),
+        }
+        .map_err(|e| Error::Input {
+            source: self.to_string(),
+            reason: self.reason(&e),
+        })?;
+        Ok(input)
+    }
+
+    fn reason(&self, e: &io::Error) -> String {
+        match (self, e.kind()) {
+            (Self::Day { .. }, io::ErrorKind::NotFound) => format!(
+                "no such file (set {} to read inputs from another directory)",
+                INPUT_DIR_VAR
+            ),
+            _ => e.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.path() {
+            Some(path) => write!(f, "{}", path.display()),
+            None => write!(f, "standard input"),
+        }
+    }
+}
+
+/// The inputs directory, `inputs` unless overridden by `AOC_INPUT_DIR`.
+pub fn dir() -> PathBuf {
+    env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("inputs"))
+}
+
+/// Where `year`'s inputs are: the inputs directory for [`YEAR`], or a
+/// subdirectory of it such as `inputs/2025`.
+pub fn year_dir(year: u16) -> PathBuf {
+    match year {
+        YEAR => dir(),
+        _ => dir().join(year.to_string()),
+    }
+}
+
+pub fn file_name(day: usize, variant: Option<&str>) -> String {
+    match variant {
+        Some(name) => format!("day{:02}_{}.txt", day, name),
+        None => format!("day{:02}.txt", day),
+    }
+}
+
+/// Reads a named variant of a day's input, e.g. `small` for the example.
+pub fn get_variant(day: usize, name: &str) -> Result<String> {
+    Source::variant(day, name).read()
+}
+
+/// [`get_variant`] for any event.
+pub fn get_year_variant(year: u16, day: usize, name: &str) -> Result<String> {
+    Source::in_year(year, day, Some(name)).read()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_names() {
+        assert_eq!("day05.txt", file_name(5, None));
+        assert_eq!("day22_small2.txt", file_name(22, Some("small2")));
+    }
+
+    #[test]
+    fn missing() {
+        let err = get_variant(5, "missing").unwrap_err();
+        assert!(err.to_string().contains("day05_missing.txt"));
+    }
+
+    #[test]
+    fn years() {
+        assert_eq!(
+            Source::variant(3, "small"),
+            Source::in_year(YEAR, 3, Some("small"))
+        );
+        assert_eq!(
+            Some(dir().join("2025").join("day01.txt")),
+            Source::in_year(2025, 1, None).path()
+        );
+    }
+}
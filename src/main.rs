@@ -1,30 +1,894 @@
-mod utils;
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-
-fn main() {
-    day25::run();
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    panic::{self, catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use rayon::prelude::*;
+
+use aoc2024::{
+    animate::{self, Playback},
+    bench::{self, Baseline, Change},
+    cache::Cache,
+    client::{Client, Fetched, Verdict},
+    differential::PAIRS,
+    error::panic_message,
+    explore::{self, Session},
+    generate, image,
+    input::{self, Source},
+    report::{self, DayReport, PartReport},
+    scaffold,
+    serve::Server,
+    solution::{days_in, solution_in, years, Answer, Params, Part, DAYS, YEAR},
+    trace::Tracing,
+    verify::{Answers, Fixture, Summary},
+};
+
+#[derive(Parser)]
+#[command(version, about = "Advent of Code 2024 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution for a single day, or `all` days
+    Run {
+        /// Day number (1-25) or `all`
+        day: Days,
+        /// Only run this part
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Read the puzzle input from this file, or `-` for standard input
+        #[arg(short, long, conflicts_with = "variant")]
+        input: Option<String>,
+        /// Read a named variant of the input, e.g. `small` for dayNN_small.txt
+        #[arg(short, long)]
+        variant: Option<String>,
+        /// How to print the results
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Threads to run `all` days on [default: one per CPU]
+        #[arg(short, long)]
+        jobs: Option<usize>,
+        /// Print where the time went, span by span, to standard error
+        #[arg(long)]
+        flame: bool,
+        /// Write every span to this file as a Chrome trace, for
+        /// chrome://tracing or Perfetto
+        #[arg(long, value_name = "FILE")]
+        chrome_trace: Option<PathBuf>,
+        /// Solve every part again instead of reusing cached answers
+        #[arg(long)]
+        no_cache: bool,
+        /// Give up on a day after this many seconds, or 0 for no limit
+        #[arg(short, long, default_value_t = 60.0)]
+        timeout: f64,
+        /// Run this event's solutions, from its `yYYYY` module
+        #[arg(short, long, default_value_t = YEAR)]
+        year: u16,
+    },
+    /// Time parse, part 1 and part 2 separately and compare against a baseline
+    Bench {
+        /// Day number (1-25) or `all`
+        day: Days,
+        /// Number of timed runs per day
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+        /// Baseline file of median times from an earlier run
+        #[arg(short, long, default_value = "bench_baseline.txt")]
+        baseline: PathBuf,
+        /// Overwrite the baseline with this run's medians
+        #[arg(short, long)]
+        save: bool,
+        /// Slowdown tolerated before flagging a regression, in percent
+        #[arg(short, long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Write a random input for a day, in the same format as the puzzle's
+    Generate {
+        /// Day number (1-25)
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Lines, items or map width of the input
+        #[arg(short, long, default_value_t = 100)]
+        size: usize,
+        /// Seed for the random generator [default: random, printed to stderr]
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Write the input to this file instead of standard output
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Watch a day's simulation step by step in the terminal
+    Animate {
+        /// Day number: 6, 14, 15 or 16
+        day: usize,
+        /// Animate the simulation for this part, where they differ
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Read the puzzle input from this file, or `-` for standard input
+        #[arg(short, long, conflicts_with = "variant")]
+        input: Option<String>,
+        /// Read a named variant of the input, e.g. `small` for dayNN_small.txt
+        #[arg(short, long)]
+        variant: Option<String>,
+        /// Frames per second
+        #[arg(short, long, default_value_t = 10.0)]
+        fps: f64,
+        /// Start paused, to step through frames one at a time
+        #[arg(long)]
+        paused: bool,
+    },
+    /// Draw a day's map as a PNG or PPM image
+    Image {
+        /// Day number: 12, 14, 16 or 20
+        day: usize,
+        /// Write the image here; a `.ppm` extension writes a PPM instead of a
+        /// PNG [default: dayNN.png]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Pixels along each side of a map tile
+        #[arg(short, long, default_value_t = 4)]
+        scale: usize,
+        /// Read the puzzle input from this file, or `-` for standard input
+        #[arg(short, long, conflicts_with = "variant")]
+        input: Option<String>,
+        /// Read a named variant of the input, e.g. `small` for dayNN_small.txt
+        #[arg(short, long)]
+        variant: Option<String>,
+    },
+    /// Explore a day's parsed puzzle model interactively
+    Repl {
+        /// Day to load: 2, 17 or 18; `load <day>` switches days later
+        day: Option<usize>,
+        /// Read the puzzle input from this file
+        #[arg(short, long, conflicts_with = "variant", requires = "day")]
+        input: Option<PathBuf>,
+        /// Read a named variant of the input, e.g. `small` for dayNN_small.txt
+        #[arg(short, long, requires = "day")]
+        variant: Option<String>,
+    },
+    /// Compare reference and optimized solvers on generated inputs
+    Diff {
+        /// Day number (1-25) or `all`
+        #[arg(default_value = "all")]
+        day: Days,
+        /// Number of seeds to generate inputs from for each size
+        #[arg(short, long, default_value_t = 20)]
+        seeds: u64,
+    },
+    /// Check answers for the full inputs against the answers file
+    Verify {
+        /// Day number (1-25) or `all`
+        #[arg(default_value = "all")]
+        day: Days,
+        /// Answers file of `day part answer` lines [default: answers.txt in
+        /// the inputs directory]
+        #[arg(short, long)]
+        answers: Option<PathBuf>,
+        /// Solve every part again instead of reusing cached answers
+        #[arg(long)]
+        no_cache: bool,
+        /// Give up on a day after this many seconds, or 0 for no limit
+        #[arg(short, long, default_value_t = 60.0)]
+        timeout: f64,
+    },
+    /// Answer puzzle inputs POSTed to /day/{n}/part/{p} with JSON over HTTP
+    Serve {
+        /// Port to listen on, on localhost only
+        #[arg(short, long, default_value_t = 8024)]
+        port: u16,
+        /// Give up on a request after this many seconds, or 0 for no limit
+        #[arg(short, long, default_value_t = 60.0)]
+        timeout: f64,
+    },
+    /// Download puzzle inputs that aren't in the inputs directory yet
+    Fetch {
+        /// Day number (1-25) or `all`
+        day: Days,
+        /// The event to download inputs for
+        #[arg(short, long, default_value_t = YEAR)]
+        year: u16,
+    },
+    /// Solve a part and submit the answer, or submit one given by hand
+    Submit {
+        /// Day number (1-25)
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Part to submit
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Submit this instead of solving the part
+        answer: Option<String>,
+        /// The event the day belongs to
+        #[arg(short, long, default_value_t = YEAR)]
+        year: u16,
+    },
+    /// Start a new day from a template, with empty input and example files
+    NewDay {
+        /// Day number (1-25)
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// The event the day belongs to, from 2025 on; each has its own
+        /// `yYYYY` module and inputs subdirectory
+        #[arg(short, long)]
+        year: u16,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// The answers as plain text
+    Text,
+    /// One JSON object per line for each day, with timings and artifacts
+    Json,
+}
+
+#[derive(Clone, Copy)]
+enum Days {
+    All,
+    One(usize),
+}
+
+impl Days {
+    fn days(&self) -> Vec<usize> {
+        self.days_in(YEAR)
+    }
+
+    /// The days selected, where `all` is every day solved for `year`.
+    fn days_in(&self, year: u16) -> Vec<usize> {
+        match self {
+            Self::All => days_in(year),
+            Self::One(day) => vec![*day],
+        }
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "all" {
+            return Ok(Self::All);
+        }
+        match s.parse() {
+            Ok(day) if (1..=DAYS).contains(&day) => Ok(Self::One(day)),
+            _ => Err(format!(
+                "expected a day between 1 and 25 or `all`, got `{}`",
+                s
+            )),
+        }
+    }
+}
+
+fn run_day(
+    year: u16,
+    day: usize,
+    part: Part,
+    source: &Source,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+) -> Result<DayReport, String> {
+    let input = source.read().map_err(|e| e.to_string())?;
+    catch_unwind(AssertUnwindSafe(|| {
+        report::run_in(year, day, &input, part, cache, timeout).map_err(|e| e.describe(&input))
+    }))
+    .map_err(|payload| format!("solution panicked: {}", panic_message(&*payload)))?
+}
+
+/// A `--timeout` in seconds as a budget, where 0 means no limit.
+fn time_budget(seconds: f64) -> Option<Duration> {
+    (seconds > 0.0).then(|| Duration::from_secs_f64(seconds))
+}
+
+fn print_text(report: &DayReport) {
+    println!("Day {:02}", report.day);
+    for part in &report.parts {
+        if part.cached {
+            println!("Part {}: {} (cached)", part.part, part.answer);
+        } else {
+            println!("Part {}: {}", part.part, part.answer);
+        }
+    }
+    for (name, artifact) in &report.artifacts {
+        println!("{}:\n{}", name, artifact.trim_end());
+    }
+}
+
+/// Prints one row per day with its answers and total time, or why it failed,
+/// followed by a count of failures. Full errors go to stderr after the table.
+fn print_table(results: &[(usize, Result<DayReport, String>)], elapsed: Duration) {
+    let rows = results
+        .iter()
+        .map(|(day, result)| {
+            let mut row = vec![format!("{:02}", day)];
+            match result {
+                Ok(report) => {
+                    for n in 1..=2 {
+                        let answer = report.parts.iter().find(|p| p.part == n);
+                        row.push(answer.map_or(String::new(), |p| p.answer.to_string()));
+                    }
+                    row.push(format!("{:.1?}", report.total_time()));
+                    row.push(String::new());
+                }
+                Err(e) => {
+                    row.extend([String::new(), String::new(), String::new()]);
+                    row.push(e.lines().next().unwrap_or_default().to_string());
+                }
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+
+    let header = ["Day", "Part 1", "Part 2", "Time", "Failure"].map(String::from);
+    let widths = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .chain([&header.to_vec()])
+                .map(|row| row[i].chars().count())
+                .max()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    for row in [&header.to_vec()].into_iter().chain(&rows) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    }
+
+    let mut failed = 0;
+    for (day, result) in results {
+        if let Err(e) = result {
+            eprintln!("Day {:02} failed: {}", day, e);
+            failed += 1;
+        }
+    }
+    let cached = results
+        .iter()
+        .filter_map(|(_, result)| result.as_ref().ok())
+        .flat_map(|report| &report.parts)
+        .filter(|part| part.cached)
+        .count();
+    println!(
+        "{} days in {:.1?}, {} failed, {} answers cached",
+        results.len(),
+        elapsed,
+        failed,
+        cached
+    );
+}
+
+/// Benchmarks one day, printing each phase and how it compares to the
+/// baseline. Returns whether any phase regressed.
+fn bench_day(
+    day: usize,
+    iterations: usize,
+    threshold: f64,
+    baseline: &Baseline,
+    medians: &mut Baseline,
+) -> Result<bool, String> {
+    let input = Source::day(day).read().map_err(|e| e.to_string())?;
+    let timings = catch_unwind(AssertUnwindSafe(|| bench::bench(day, &input, iterations)))
+        .map_err(|_| "solution panicked".to_string())?
+        .map_err(|e| e.describe(&input))?;
+    let mut regressed = false;
+    for (phase, stats) in timings {
+        medians.insert(day, phase, stats.median);
+        match baseline.get(day, phase) {
+            Some(old) => {
+                let change = Change::new(stats.median, old, threshold / 100.0);
+                regressed |= matches!(change, Change::Regression(_));
+                println!("  {:<6} {}  {}", phase, stats, change);
+            }
+            None => println!("  {:<6} {}", phase, stats),
+        }
+    }
+    Ok(regressed)
+}
+
+/// Runs both parts of a day on its full input and checks them against the
+/// expected answers.
+fn verify_day(
+    day: usize,
+    answers: &Answers,
+    summary: &mut Summary,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+) -> Result<(), String> {
+    let report = run_day(YEAR, day, Part::Both, &Source::day(day), cache, timeout)?;
+    for PartReport { part, answer, .. } in &report.parts {
+        let outcome = answers.check(day, *part, answer);
+        println!("Day {:02} part {}: {}", day, part, outcome);
+        summary.add(&outcome);
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            variant,
+            format,
+            jobs,
+            flame,
+            chrome_trace,
+            no_cache,
+            timeout,
+            year,
+        } => {
+            if !years().contains(&year) {
+                eprintln!("no solutions for {}; start some with `new-day`", year);
+                return ExitCode::FAILURE;
+            }
+            if let Days::One(day) = day {
+                if solution_in(year, day).is_none() {
+                    eprintln!("day {} of {} isn't solved yet", day, year);
+                    return ExitCode::FAILURE;
+                }
+            }
+            let part = Part::new(part);
+            let timeout = time_budget(timeout);
+            let cache = (!no_cache).then(|| Cache::new(Cache::default_dir()));
+            let cache = cache.as_ref();
+            if matches!(day, Days::All) && input.is_some() {
+                eprintln!("--input can only be used with a single day");
+                return ExitCode::FAILURE;
+            }
+            let tracing = match Tracing::start(flame, chrome_trace.as_deref()) {
+                Ok(tracing) => tracing,
+                Err(e) => {
+                    eprintln!("could not start tracing: {}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let source = |day| match &input {
+                Some(input) => Source::from_arg(input),
+                None => Source::in_year(year, day, variant.as_deref()),
+            };
+
+            let start = Instant::now();
+            let results = match day {
+                Days::One(day) => {
+                    vec![(day, run_day(year, day, part, &source(day), cache, timeout))]
+                }
+                Days::All => {
+                    let pool = match rayon::ThreadPoolBuilder::new()
+                        .num_threads(jobs.unwrap_or(0))
+                        .build()
+                    {
+                        Ok(pool) => pool,
+                        Err(e) => {
+                            eprintln!("could not start threads: {}", e);
+                            return ExitCode::FAILURE;
+                        }
+                    };
+                    // Panics are reported in the results, so keep the default
+                    // hook from printing over the table
+                    let hook = panic::take_hook();
+                    panic::set_hook(Box::new(|_| {}));
+                    let results = pool.install(|| {
+                        day.days_in(year)
+                            .into_par_iter()
+                            .map(|day| {
+                                let result = run_day(year, day, part, &source(day), cache, timeout);
+                                (day, result)
+                            })
+                            .collect::<Vec<_>>()
+                    });
+                    panic::set_hook(hook);
+                    results
+                }
+            };
+            let elapsed = start.elapsed();
+
+            match (day, format) {
+                (Days::All, Format::Text) => print_table(&results, elapsed),
+                (_, Format::Text) => {
+                    for (day, result) in &results {
+                        match result {
+                            Ok(report) => print_text(report),
+                            Err(e) => eprintln!("Day {:02} failed: {}", day, e),
+                        }
+                    }
+                }
+                (_, Format::Json) => {
+                    for (day, result) in &results {
+                        match result {
+                            Ok(report) => println!("{}", serde_json::to_string(report).unwrap()),
+                            Err(e) => println!("{}", serde_json::json!({ "day": day, "error": e })),
+                        }
+                    }
+                }
+            }
+            if let Some(summary) = tracing.finish() {
+                eprint!("\n{}", summary);
+            }
+            if results.iter().any(|(_, result)| result.is_err()) {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Bench {
+            day,
+            iterations,
+            baseline: path,
+            save,
+            threshold,
+        } => {
+            let baseline = match Baseline::load(&path) {
+                Ok(baseline) => baseline,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            // Keep entries for days not benchmarked this time
+            let mut medians = baseline.clone();
+
+            let mut failed = false;
+            let mut regressed = false;
+            for day in day.days() {
+                println!("Day {:02}", day);
+                match bench_day(day, iterations, threshold, &baseline, &mut medians) {
+                    Ok(r) => regressed |= r,
+                    Err(e) => {
+                        eprintln!("Day {:02} failed: {}", day, e);
+                        failed = true;
+                    }
+                }
+            }
+
+            // Phases without a baseline yet are recorded even without --save
+            if save || medians.len() > baseline.len() {
+                if let Err(e) = medians.save(&path) {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+                println!("Saved baseline to {}", path.display());
+            }
+            if regressed {
+                eprintln!("Regressions against {}", path.display());
+            }
+            if failed || regressed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Generate {
+            day,
+            size,
+            seed,
+            output,
+        } => {
+            let seed = seed.unwrap_or_else(|| {
+                let seed = rand::random();
+                eprintln!("seed {}", seed);
+                seed
+            });
+            let input =
+                generate::generate(day as usize, size, seed).expect("every day has a generator");
+            let written = match &output {
+                Some(path) => fs::write(path, input)
+                    .map_err(|e| format!("could not write {}: {}", path.display(), e)),
+                None => io::stdout()
+                    .write_all(input.as_bytes())
+                    .map_err(|e| e.to_string()),
+            };
+            match written {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Animate {
+            day,
+            part,
+            input,
+            variant,
+            fps,
+            paused,
+        } => {
+            let source = match (&input, &variant) {
+                (Some(input), _) => Source::from_arg(input),
+                (_, Some(variant)) => Source::variant(day, variant),
+                _ => Source::day(day),
+            };
+            let frames = source.read().map_err(|e| e.to_string()).and_then(|text| {
+                let days = animate::days().map(|d| d.to_string()).collect::<Vec<_>>();
+                animate::animation(day, &text, Part::new(part))
+                    .ok_or_else(|| {
+                        format!("day {} can't be animated, try {}", day, days.join(", "))
+                    })?
+                    .map_err(|e| e.describe(&text))
+            });
+            let played = frames.and_then(|frames| {
+                let fps = fps.max(0.25);
+                animate::play(frames, Playback { fps, paused }).map_err(|e| e.to_string())
+            });
+            match played {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Image {
+            day,
+            output,
+            scale,
+            input,
+            variant,
+        } => {
+            let source = match (&input, &variant) {
+                (Some(input), _) => Source::from_arg(input),
+                (_, Some(variant)) => Source::variant(day, variant),
+                _ => Source::day(day),
+            };
+            let path = output.unwrap_or_else(|| PathBuf::from(format!("day{:02}.png", day)));
+            let saved = source.read().map_err(|e| e.to_string()).and_then(|text| {
+                let days = image::days().map(|d| d.to_string()).collect::<Vec<_>>();
+                image::picture(day, &text, scale)
+                    .ok_or_else(|| format!("day {} can't be drawn, try {}", day, days.join(", ")))?
+                    .map_err(|e| e.describe(&text))?
+                    .save(&path)
+                    .map_err(|e| format!("{}: {}", path.display(), e))
+            });
+            match saved {
+                Ok(()) => {
+                    eprintln!("wrote {}", path.display());
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Repl {
+            day,
+            input,
+            variant,
+        } => {
+            let mut session = Session::new();
+            if let Some(day) = day {
+                // Examples bring the puzzle parameters in their sidecars
+                let params = match &variant {
+                    Some(variant) => Fixture::params(YEAR, day, variant),
+                    None => Ok(Params::new()),
+                };
+                let source = match (input, &variant) {
+                    (Some(input), _) => Source::File(input),
+                    (_, Some(variant)) => Source::variant(day, variant),
+                    _ => Source::day(day),
+                };
+                let loaded = params
+                    .and_then(|params| Ok((params, source.read()?)))
+                    .map_err(|e| e.to_string())
+                    .and_then(|(params, text)| session.load(day, &text, &params));
+                if let Err(e) = loaded {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            }
+            // Panicking commands are reported as errors
+            panic::set_hook(Box::new(|_| {}));
+            let stdin = io::stdin();
+            let prompt = stdin.is_terminal();
+            match explore::repl(&mut session, stdin.lock(), &mut io::stdout(), prompt) {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Diff { day, seeds } => {
+            let days = day.days();
+            let pairs = PAIRS.iter().filter(|pair| days.contains(&pair.day));
+            // Panics are reported as outcomes
+            panic::set_hook(Box::new(|_| {}));
+            let mut disagreed = false;
+            for pair in pairs {
+                let comparison = pair.compare(0..seeds);
+                match comparison.smallest {
+                    None => println!(
+                        "Day {:02} {}: agree on {} inputs",
+                        pair.day, pair.name, comparison.inputs
+                    ),
+                    Some(d) => {
+                        disagreed = true;
+                        println!(
+                            "Day {:02} {}: disagree, smallest input is size {} seed {}",
+                            pair.day, pair.name, d.size, d.seed
+                        );
+                        println!("{}", d.input.trim_end());
+                        for (name, outcome) in
+                            [("reference", d.reference), ("optimized", d.optimized)]
+                        {
+                            match outcome {
+                                Ok(answer) => println!("  {}: {}", name, answer),
+                                Err(e) => println!("  {}: {}", name, e),
+                            }
+                        }
+                    }
+                }
+            }
+            if disagreed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Verify {
+            day,
+            answers,
+            no_cache,
+            timeout,
+        } => {
+            let timeout = time_budget(timeout);
+            let cache = (!no_cache).then(|| Cache::new(Cache::default_dir()));
+            let path = answers.unwrap_or_else(Answers::default_path);
+            let answers = match Answers::load(&path) {
+                Ok(answers) => answers,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let mut summary = Summary::default();
+            for day in day.days() {
+                if let Err(e) = verify_day(day, &answers, &mut summary, cache.as_ref(), timeout) {
+                    eprintln!("Day {:02} failed: {}", day, e);
+                    // Every part with an answer to compare failed
+                    summary.failed += answers.parts(day).len();
+                }
+            }
+            println!("{}", summary);
+            if summary.failed > 0 {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Serve { port, timeout } => {
+            let server = match Server::bind(("127.0.0.1", port), time_budget(timeout)) {
+                Ok(server) => server,
+                Err(e) => {
+                    eprintln!("could not listen on port {}: {}", port, e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            eprintln!("listening on http://{}", server.addr());
+            server.run();
+            ExitCode::SUCCESS
+        }
+        Command::Fetch { day, year } => {
+            let client = match Client::from_env() {
+                Ok(client) => client.year(year),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            // Every day with an input, solved or not; days unlock in order
+            let single = matches!(day, Days::One(_));
+            let days = match day {
+                Days::All => (1..=DAYS).collect(),
+                Days::One(day) => vec![day],
+            };
+            let mut failed = false;
+            for day in days {
+                match client.fetch(day, &input::year_dir(year)) {
+                    Ok(Fetched::Downloaded(path)) => eprintln!("wrote {}", path.display()),
+                    Ok(Fetched::Cached(path)) => eprintln!("{} is already there", path.display()),
+                    Ok(Fetched::Locked) => {
+                        eprintln!("day {} of {} isn't unlocked yet", day, year);
+                        // Later days can't be unlocked either
+                        failed |= single;
+                        break;
+                    }
+                    Err(e) => {
+                        eprintln!("Day {:02} failed: {}", day, e);
+                        failed = true;
+                    }
+                }
+            }
+            if failed {
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
+        Command::Submit {
+            day,
+            part,
+            answer,
+            year,
+        } => {
+            let client = match Client::from_env() {
+                Ok(client) => client.year(year),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let day = day as usize;
+            let answer = match answer {
+                Some(answer) => answer,
+                None => {
+                    if solution_in(year, day).is_none() {
+                        eprintln!("day {} of {} isn't solved yet", day, year);
+                        return ExitCode::FAILURE;
+                    }
+                    let source = Source::in_year(year, day, None);
+                    let report =
+                        match run_day(year, day, Part::new(Some(part)), &source, None, None) {
+                            Ok(report) => report,
+                            Err(e) => {
+                                eprintln!("Day {:02} failed: {}", day, e);
+                                return ExitCode::FAILURE;
+                            }
+                        };
+                    match &report.parts[0].answer {
+                        Answer::None => {
+                            eprintln!("day {} part {} has no answer to submit", day, part);
+                            return ExitCode::FAILURE;
+                        }
+                        answer => answer.to_string(),
+                    }
+                }
+            };
+            eprintln!("submitting {} for day {} part {}", answer, day, part);
+            match client.submit(day, part, &answer) {
+                Ok(Verdict::Correct) => {
+                    println!("{}", Verdict::Correct);
+                    ExitCode::SUCCESS
+                }
+                Ok(verdict) => {
+                    println!("{}", verdict);
+                    ExitCode::FAILURE
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::NewDay { day, year } => {
+            match scaffold::new_day(Path::new("."), year, day as usize) {
+                Ok(written) => {
+                    for path in written {
+                        eprintln!("wrote {}", path.display());
+                    }
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    ExitCode::FAILURE
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,194 @@
+use std::{fmt, str::FromStr};
+
+/// Malformed puzzle input. Lines and columns count from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: Option<usize>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    /// An error at `token`, which has to be a slice of `input` for the
+    /// position to be right.
+    pub fn new(input: &str, token: &str, expected: impl Into<String>) -> Self {
+        let (line, column) = position(input, token);
+        let found = match token.chars().next() {
+            None => "nothing".to_string(),
+            Some(_) if token.chars().count() > 20 => {
+                format!("`{}...`", token.chars().take(20).collect::<String>())
+            }
+            Some(_) => format!("`{}`", token),
+        };
+        Self {
+            day: None,
+            line,
+            column,
+            expected: expected.into(),
+            found,
+        }
+    }
+
+    /// An error for input that stops before `expected` shows up, placed just
+    /// after its last token.
+    pub fn eof(input: &str, expected: impl Into<String>) -> Self {
+        Self {
+            found: "end of input".to_string(),
+            ..Self::new(input, &input[input.trim_end().len()..], expected)
+        }
+    }
+
+    /// Moves an error found while parsing `inner`, a slice of `outer`, so its
+    /// position is relative to `outer`.
+    pub fn within(mut self, outer: &str, inner: &str) -> Self {
+        let (line, column) = position(outer, inner);
+        if self.line == 1 {
+            self.column += column - 1;
+        }
+        self.line += line - 1;
+        self
+    }
+
+    pub fn with_day(mut self, day: usize) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// Renders the error along with the offending line of `input`.
+    pub fn render(&self, input: &str) -> String {
+        let source = input.lines().nth(self.line - 1).unwrap_or("");
+        let gutter = " ".repeat(self.line.to_string().len());
+        format!(
+            "error: expected {}, found {}\n{}--> {}\n{} |\n{} | {}\n{} | {}^",
+            self.expected,
+            self.found,
+            gutter,
+            self.location(),
+            gutter,
+            self.line,
+            source,
+            gutter,
+            " ".repeat(self.column - 1)
+        )
+    }
+
+    fn location(&self) -> String {
+        match self.day {
+            Some(day) => format!("day {}, line {}, column {}", day, self.line, self.column),
+            None => format!("line {}, column {}", self.line, self.column),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: expected {}, found {}",
+            self.location(),
+            self.expected,
+            self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Line and column of `token` inside `input`, falling back to the start when
+/// `token` isn't a slice of `input`.
+fn position(input: &str, token: &str) -> (usize, usize) {
+    let start = input.as_ptr() as usize;
+    let offset = (token.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|offset| *offset <= input.len())
+        .unwrap_or(0);
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap().chars().count() + 1;
+    (line, column)
+}
+
+/// Parses `token`, a slice of `input`, into a number.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::new(input, token, "number"))
+}
+
+/// Parses every line of `input` with `f`.
+pub fn lines<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .map(|line| f(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+/// Parses every blank line separated block of `input` with `f`.
+pub fn blocks<'a, T>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .split("\n\n")
+        .map(|block| f(block).map_err(|e| e.within(input, block)))
+        .collect()
+}
+
+/// Takes the next token from `tokens`, all slices of `input`.
+pub fn next<'a>(
+    input: &str,
+    tokens: &mut impl Iterator<Item = &'a str>,
+    expected: &str,
+) -> Result<&'a str, ParseError> {
+    tokens
+        .next()
+        .ok_or_else(|| ParseError::eof(input, expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position() {
+        let input = "1 2\n3 x 4\n";
+        let line = input.lines().nth(1).unwrap();
+        let err = number::<u32>(line, &line[2..3])
+            .unwrap_err()
+            .within(input, line);
+        assert_eq!((2, 3), (err.line, err.column));
+        assert_eq!(
+            "line 2, column 3: expected number, found `x`",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn eof() {
+        let input = "1 2\n3";
+        let line = input.lines().nth(1).unwrap();
+        let err = next(line, &mut line.split_whitespace().skip(1), "number")
+            .unwrap_err()
+            .within(input, line)
+            .with_day(1);
+        assert_eq!(
+            "day 1, line 2, column 2: expected number, found end of input",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn render() {
+        let input = "1 2\n3 x 4\n";
+        let err = ParseError::new(input, &input[6..7], "number");
+        assert_eq!(
+            "error: expected number, found `x`\n --> line 2, column 3\n  |\n2 | 3 x 4\n  |   ^",
+            err.render(input)
+        );
+    }
+}
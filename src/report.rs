@@ -0,0 +1,238 @@
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use serde::{Serialize, Serializer};
+
+use crate::{
+    cache::{Cache, Entry, Key},
+    cancel::{TimedOut, Token},
+    error::{Error, Result},
+    solution::{solution_in, Answer, Params, Part, YEAR},
+};
+
+/// The outcome of running one day: each requested part's answer with how
+/// long it took, plus any artifacts part 2 produced.
+#[derive(Clone, Debug, Serialize)]
+pub struct DayReport {
+    pub day: usize,
+    #[serde(rename = "parse_ns", serialize_with = "nanos")]
+    pub parse_time: Duration,
+    pub parts: Vec<PartReport>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub artifacts: BTreeMap<String, String>,
+}
+
+impl DayReport {
+    /// Time spent parsing and solving, all parts together.
+    pub fn total_time(&self) -> Duration {
+        self.parse_time + self.parts.iter().map(|p| p.time).sum::<Duration>()
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Answer,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    #[serde(rename = "time_ns", serialize_with = "nanos")]
+    pub time: Duration,
+    /// Whether the answer came from the cache rather than being solved.
+    pub cached: bool,
+}
+
+fn nanos<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_nanos() as u64)
+}
+
+/// [`run_in`] for a day of [`YEAR`].
+pub fn run(
+    day: usize,
+    input: &str,
+    part: Part,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+) -> Result<DayReport> {
+    run_in(YEAR, day, input, part, cache, timeout)
+}
+
+/// Parses `input` for `day` of `year` and solves the parts selected by `part`, timing
+/// each phase. Parts found in `cache` aren't solved again, and the input is
+/// only parsed if some part isn't cached. Parts without an answer are never
+/// cached. Solving stops with
+/// [`Error::TimedOut`] once `timeout` has passed.
+pub fn run_in(
+    year: u16,
+    day: usize,
+    input: &str,
+    part: Part,
+    cache: Option<&Cache>,
+    timeout: Option<Duration>,
+) -> Result<DayReport> {
+    match timeout {
+        Some(budget) => Token::after(budget)
+            .run(|| solve(year, day, input, part, cache))
+            .map_err(|TimedOut| Error::TimedOut(budget))?,
+        None => solve(year, day, input, part, cache),
+    }
+}
+
+fn solve(
+    year: u16,
+    day: usize,
+    input: &str,
+    part: Part,
+    cache: Option<&Cache>,
+) -> Result<DayReport> {
+    let parse = solution_in(year, day).expect("every day is registered");
+    let mut solver = None;
+    let mut parse_time = Duration::ZERO;
+    let mut parts = Vec::new();
+    let mut artifacts = BTreeMap::new();
+    for n in [1, 2] {
+        if (n == 1 && !part.one()) || (n == 2 && !part.two()) {
+            continue;
+        }
+        let start = Instant::now();
+        let key = Key::new(year, day, n, input);
+        if let Some(entry) = cache.and_then(|cache| cache.get(&key)) {
+            parts.push(PartReport {
+                part: n,
+                kind: entry.answer.kind(),
+                answer: entry.answer,
+                time: start.elapsed(),
+                cached: true,
+            });
+            artifacts.extend(entry.artifacts);
+            continue;
+        }
+
+        if solver.is_none() {
+            let start = Instant::now();
+            solver = Some(parse(input, &Params::new())?);
+            parse_time = start.elapsed();
+        }
+        let solver = solver.as_ref().unwrap();
+        let start = Instant::now();
+        let answer = match n {
+            1 => solver.part1(),
+            _ => solver.part2(),
+        };
+        let time = start.elapsed();
+        // Artifacts go with part 2
+        let made = match n {
+            1 => BTreeMap::new(),
+            _ => solver
+                .artifacts()
+                .into_iter()
+                .map(|(name, artifact)| (name.to_string(), artifact))
+                .collect(),
+        };
+        // A missing answer is usually a part not written yet, which mustn't
+        // stick once it is
+        if let Some(cache) = cache.filter(|_| answer != Answer::None) {
+            let entry = Entry {
+                answer: answer.clone(),
+                artifacts: made.clone(),
+            };
+            cache.put(&key, &entry);
+        }
+        artifacts.extend(made);
+        parts.push(PartReport {
+            part: n,
+            kind: answer.kind(),
+            answer,
+            time,
+            cached: false,
+        });
+    }
+
+    Ok(DayReport {
+        day,
+        parse_time,
+        parts,
+        artifacts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::get_variant;
+
+    use super::*;
+
+    #[test]
+    fn json() {
+        let report = run(1, &get_variant(1, "small").unwrap(), Part::Both, None, None).unwrap();
+        let value = serde_json::to_value(&report).unwrap();
+        assert_eq!(1, value["day"]);
+        assert_eq!(11, value["parts"][0]["answer"]);
+        assert_eq!("uint", value["parts"][0]["type"]);
+        assert_eq!(2, value["parts"][1]["part"]);
+        assert!(value["parts"][1]["time_ns"].is_u64());
+        assert!(value.get("artifacts").is_none());
+
+        let report = run(
+            24,
+            &get_variant(24, "small").unwrap(),
+            Part::Two,
+            None,
+            None,
+        )
+        .unwrap();
+        let value = serde_json::to_value(&report).unwrap();
+        assert_eq!(1, value["parts"].as_array().unwrap().len());
+        assert!(value["parts"][0]["answer"].is_null());
+        assert!(value["artifacts"]["dot"]
+            .as_str()
+            .unwrap()
+            .starts_with("digraph"));
+    }
+
+    #[test]
+    fn cached() {
+        let dir = std::env::temp_dir().join(format!("aoc2024-report-{}", std::process::id()));
+        let cache = Cache::new(&dir);
+        let input = get_variant(14, "small").unwrap();
+        let report = run(14, &input, Part::Both, Some(&cache), None).unwrap();
+        assert!(report.parts.iter().all(|p| !p.cached));
+
+        let again = run(14, &input, Part::Two, Some(&cache), None).unwrap();
+        assert_eq!(Duration::ZERO, again.parse_time);
+        assert!(again.parts[0].cached);
+        assert_eq!(report.parts[1].answer, again.parts[0].answer);
+        assert_eq!(report.artifacts, again.artifacts);
+
+        let changed = run(
+            14,
+            &format!("{}p=0,0 v=1,1\n", input),
+            Part::Two,
+            Some(&cache),
+            None,
+        );
+        assert!(!changed.unwrap().parts[0].cached);
+
+        let input = get_variant(24, "small").unwrap();
+        run(24, &input, Part::Both, Some(&cache), None).unwrap();
+        let again = run(24, &input, Part::Both, Some(&cache), None).unwrap();
+        assert!(again.parts[0].cached);
+        assert_eq!(Answer::None, again.parts[1].answer);
+        assert!(!again.parts[1].cached);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn timeout() {
+        // The guard walks in a square forever
+        let input = ".#..\n...#\n#^..\n..#.\n";
+        let budget = Duration::from_millis(50);
+        assert_eq!(
+            Err(Error::TimedOut(budget)),
+            run(6, input, Part::One, None, Some(budget)).map(|_| ())
+        );
+        let small = get_variant(6, "small").unwrap();
+        assert!(run(6, &small, Part::Both, None, Some(Duration::from_secs(60))).is_ok());
+    }
+}
@@ -0,0 +1,368 @@
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use crate::solution::{DAYS, YEAR};
+
+/// A new day's module, with `{year}` and `{day}` filled in.
+const DAY_TEMPLATE: &str = r#"use crate::{
+    error::Result,
+    parse::ParseError,
+    solution::{Answer, Solution},
+};
+
+pub struct Puzzle {
+    pub lines: Vec<String>,
+}
+
+impl Puzzle {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let lines = input.lines().map(str::to_string).collect();
+        Ok(Self { lines })
+    }
+}
+
+impl Solution for Puzzle {
+    const DAY: usize = {day};
+    // Bump this whenever a change could alter the answers, or `run` keeps
+    // returning the ones cached from before
+    const VERSION: u32 = 1;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Puzzle::new(input)?)
+    }
+
+    fn part1(&self) -> Answer {
+        Answer::None
+    }
+
+    fn part2(&self) -> Answer {
+        Answer::None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::get_year_variant;
+
+    use super::*;
+
+    #[test]
+    fn part1() {
+        let puzzle = Puzzle::new(&get_year_variant({year}, {day}, "small").unwrap()).unwrap();
+        assert_eq!(Answer::None, puzzle.part1())
+    }
+}
+"#;
+
+/// A new event's module, with `{year}` filled in. The first day is added to
+/// it straight away.
+const YEAR_TEMPLATE: &str = r#"//! Advent of Code {year}, registered with the dispatcher in
+//! [`crate::solution`].
+
+use crate::solution::{parser, version_of, Registry};
+
+/// Every solved day, in order.
+pub const SOLUTIONS: Registry = &[];
+"#;
+
+/// A new example's answers sidecar.
+const ANSWERS_TEMPLATE: &str = "\
+# `part answer` for the example in dayNN_small.txt, e.g.
+# 1 143
+";
+
+const SOLUTIONS: &str = "pub const SOLUTIONS: Registry = &[";
+const EVENTS: &str = "const EVENTS: &[(u16, Registry)] = &[";
+
+/// Adds day `day` of `year` to the crate at `root`: its module from a
+/// template, registered with the dispatcher, and empty input, example and
+/// answers files. The first day of a new event creates its `yYYYY` module
+/// and inputs directory as well. Returns the files written, relative to
+/// `root`.
+///
+/// Days of [`YEAR`] all exist already, so they can't be added. Input files
+/// that already exist are left alone.
+pub fn new_day(root: &Path, year: u16, day: usize) -> io::Result<Vec<PathBuf>> {
+    if !(1..=DAYS).contains(&day) {
+        return Err(error(format!("expected a day between 1 and {}", DAYS)));
+    }
+    if year == YEAR {
+        return Err(error(format!(
+            "src/day{:02}.rs already exists; new days go in another event, e.g. --year {}",
+            day,
+            YEAR + 1
+        )));
+    }
+    if !root.join("src/solution.rs").exists() {
+        return Err(error(format!(
+            "no src/solution.rs in {}; run from the crate root",
+            root.display()
+        )));
+    }
+
+    let event = format!("y{}", year);
+    let dd = format!("day{:02}", day);
+    let module = PathBuf::from(format!("src/{}/{}.rs", event, dd));
+    if root.join(&module).exists() {
+        return Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{} already exists", module.display()),
+        ));
+    }
+
+    let mut written = Vec::new();
+    let registry = PathBuf::from(format!("src/{}/mod.rs", event));
+    let source = match read(root, &registry)? {
+        Some(source) => source,
+        None => {
+            fs::create_dir_all(root.join("src").join(&event))?;
+            edit(root, "src/lib.rs", |lib| Some(add_module(lib, &event)))?;
+            edit(root, "src/solution.rs", |solution| {
+                let entry = format!("({}, crate::{}::SOLUTIONS)", year, event);
+                add_entry(solution, EVENTS, &entry)
+            })?;
+            written.extend(["src/lib.rs".into(), "src/solution.rs".into()]);
+            YEAR_TEMPLATE.replace("{year}", &year.to_string())
+        }
+    };
+    let entry = format!(
+        "({}, parser::<{dd}::Puzzle>, version_of::<{dd}::Puzzle>())",
+        day,
+        dd = dd
+    );
+    let source = add_entry(&add_module(&source, &dd), SOLUTIONS, &entry)
+        .ok_or_else(|| error(format!("no SOLUTIONS in {}", registry.display())))?;
+    fs::write(root.join(&registry), source)?;
+    written.push(registry);
+
+    let template = DAY_TEMPLATE
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string());
+    fs::write(root.join(&module), template)?;
+    written.push(module);
+
+    let inputs = PathBuf::from("inputs").join(year.to_string());
+    fs::create_dir_all(root.join(&inputs))?;
+    for (name, contents) in [
+        (format!("{}.txt", dd), ""),
+        (format!("{}_small.txt", dd), ""),
+        (format!("{}_small.answers", dd), ANSWERS_TEMPLATE),
+    ] {
+        let path = inputs.join(name);
+        if !root.join(&path).exists() {
+            fs::write(root.join(&path), contents)?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+fn error(message: String) -> io::Error {
+    io::Error::new(ErrorKind::InvalidInput, message)
+}
+
+/// Reads a file under `root`, or `None` if there isn't one.
+fn read(root: &Path, path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(root.join(path)) {
+        Ok(source) => Ok(Some(source)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Rewrites a source file under `root` with `f`, which returns `None` if it
+/// can't find what it's looking for.
+fn edit(root: &Path, path: &str, f: impl FnOnce(&str) -> Option<String>) -> io::Result<()> {
+    let source = fs::read_to_string(root.join(path))?;
+    let source = f(&source).ok_or_else(|| error(format!("{} isn't laid out as expected", path)))?;
+    fs::write(root.join(path), source)
+}
+
+/// Adds `pub mod name;` to the `pub mod` lines in `source`, keeping them
+/// sorted, or after the imports if there are none yet.
+fn add_module(source: &str, name: &str) -> String {
+    let declaration = format!("pub mod {};", name);
+    let mut lines = source.lines().map(str::to_string).collect::<Vec<_>>();
+    let modules = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod "))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    match (modules.first(), modules.last()) {
+        (Some(&first), Some(&last)) => {
+            let mut block = lines.splice(first..=last, []).collect::<Vec<_>>();
+            block.push(declaration);
+            block.sort();
+            block.dedup();
+            lines.splice(first..first, block);
+        }
+        _ => {
+            let at = lines
+                .iter()
+                .rposition(|line| line.starts_with("use "))
+                .map_or(0, |i| i + 2);
+            lines.splice(at..at, [declaration, String::new()]);
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Adds `entry` to the array that starts with `open` in `source`, ordered by
+/// the number it starts with. The array is laid out the way rustfmt would:
+/// on one line if it's short, otherwise one entry per line.
+fn add_entry(source: &str, open: &str, entry: &str) -> Option<String> {
+    let start = source.find(open)? + open.len();
+    let end = start + source[start..].find("];")?;
+    let mut entries = split_entries(&source[start..end]);
+    entries.push(entry.to_string());
+    entries.sort_by_key(|entry| {
+        let digits = entry
+            .trim_start_matches('(')
+            .split(|c: char| !c.is_ascii_digit())
+            .next()
+            .unwrap_or_default();
+        digits.parse::<u32>().unwrap_or(u32::MAX)
+    });
+
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let indent = &source[line_start..start - open.len()];
+    let inline = entries.join(", ");
+    let array = if inline.len() + 2 <= 60 && open.len() + inline.len() + 2 <= 100 - indent.len() {
+        inline
+    } else {
+        let mut array = String::from("\n");
+        for entry in &entries {
+            array += &format!("{}    {},\n", indent, entry);
+        }
+        array + indent
+    };
+    Some(format!("{}{}{}", &source[..start], array, &source[end..]))
+}
+
+/// The entries of an array, split on the commas outside parentheses.
+fn split_entries(array: &str) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    for c in array.chars() {
+        match c {
+            '(' | '<' => depth += 1,
+            ')' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    entries.push(current);
+    entries
+        .into_iter()
+        .map(|entry| entry.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn entries() {
+        let source = "const EVENTS: &[(u16, Registry)] = &[];\n";
+        let source = add_entry(source, EVENTS, "(2026, crate::y2026::SOLUTIONS)").unwrap();
+        assert_eq!(
+            "const EVENTS: &[(u16, Registry)] = &[(2026, crate::y2026::SOLUTIONS)];\n",
+            source
+        );
+        let source = add_entry(&source, EVENTS, "(2025, crate::y2025::SOLUTIONS)").unwrap();
+        assert_eq!(
+            vec![
+                "(2025, crate::y2025::SOLUTIONS)",
+                "(2026, crate::y2026::SOLUTIONS)"
+            ],
+            split_entries(&source[EVENTS.len()..source.find("];").unwrap()])
+        );
+
+        let mut source = format!("{}];\n", SOLUTIONS);
+        for day in [10, 2, 1] {
+            let entry = format!(
+                "({}, parser::<day{:02}::Puzzle>, version_of::<day{:02}::Puzzle>())",
+                day, day, day
+            );
+            source = add_entry(&source, SOLUTIONS, &entry).unwrap();
+        }
+        let days = source
+            .lines()
+            .skip(1)
+            .map(|line| line.trim().split(',').next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["(1", "(2", "(10", "];"], days);
+    }
+
+    #[test]
+    fn modules() {
+        let lib = "pub mod cache;\npub mod verify;\n";
+        assert_eq!(
+            "pub mod cache;\npub mod verify;\npub mod y2025;\n",
+            add_module(lib, "y2025")
+        );
+        let registry = add_module(&YEAR_TEMPLATE.replace("{year}", "2025"), "day03");
+        let registry = add_module(&registry, "day01");
+        assert!(registry.contains("Registry};\n\npub mod day01;\npub mod day03;\n\n/// Every"));
+    }
+
+    #[test]
+    fn scaffold() {
+        let root = env::temp_dir().join(format!("aoc2024-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), "pub mod solution;\n").unwrap();
+        fs::write(root.join("src/solution.rs"), format!("{}];\n", EVENTS)).unwrap();
+
+        let written = new_day(&root, 2025, 3).unwrap();
+        assert!(written.contains(&PathBuf::from("src/y2025/day03.rs")));
+        assert!(written.contains(&PathBuf::from("inputs/2025/day03_small.answers")));
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        assert!(lib.ends_with("pub mod solution;\npub mod y2025;\n"));
+        let solution = fs::read_to_string(root.join("src/solution.rs")).unwrap();
+        assert!(solution.contains("(2025, crate::y2025::SOLUTIONS)"));
+        let module = fs::read_to_string(root.join("src/y2025/day03.rs")).unwrap();
+        assert!(module.contains("const DAY: usize = 3;"));
+        assert!(module.contains("const VERSION: u32 = 1;"));
+        assert!(module.contains("get_year_variant(2025, 3, \"small\")"));
+
+        fs::write(root.join("inputs/2025/day01.txt"), "downloaded").unwrap();
+        let written = new_day(&root, 2025, 1).unwrap();
+        assert_eq!(
+            vec![
+                "src/y2025/mod.rs",
+                "src/y2025/day01.rs",
+                "inputs/2025/day01_small.txt",
+                "inputs/2025/day01_small.answers"
+            ],
+            written
+                .iter()
+                .map(|p| p.to_str().unwrap())
+                .collect::<Vec<_>>()
+        );
+        let input = fs::read_to_string(root.join("inputs/2025/day01.txt")).unwrap();
+        assert_eq!("downloaded", input);
+        let registry = fs::read_to_string(root.join("src/y2025/mod.rs")).unwrap();
+        assert!(registry.find("pub mod day01;") < registry.find("pub mod day03;"));
+        assert!(registry.find("(1, parser") < registry.find("(3, parser"));
+
+        let err = new_day(&root, 2025, 3).unwrap_err();
+        assert_eq!(ErrorKind::AlreadyExists, err.kind());
+        assert!(new_day(&root, YEAR, 3).is_err());
+        assert!(new_day(&root, 2025, 26).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
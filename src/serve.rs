@@ -0,0 +1,181 @@
+use std::{
+    io,
+    net::{SocketAddr, ToSocketAddrs},
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use serde_json::{json, Value};
+use tiny_http::{Header, Method, Request, Response};
+
+use crate::{
+    error::{panic_message, Error},
+    report,
+    solution::{solution, Part},
+};
+
+/// The status and JSON body to answer a request with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reply {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Reply {
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+/// Solves puzzle inputs sent over HTTP:
+///
+/// - `POST /day/{n}/part/{p}` solves one part of the input in the body
+/// - `POST /day/{n}` solves both parts
+///
+/// Answers come back as the JSON `run --format json` prints, with timings.
+/// Inputs that don't parse get a 422 saying where and why. Inputs sent this
+/// way are one-offs, so their answers aren't cached.
+pub struct Server {
+    http: tiny_http::Server,
+    timeout: Option<Duration>,
+}
+
+impl Server {
+    /// Listens on `addr`, giving up on solving after `timeout`.
+    pub fn bind(addr: impl ToSocketAddrs, timeout: Option<Duration>) -> io::Result<Self> {
+        let http = tiny_http::Server::http(addr).map_err(io::Error::other)?;
+        Ok(Self { http, timeout })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.http
+            .server_addr()
+            .to_ip()
+            .expect("bound to an IP address")
+    }
+
+    /// Serves requests until the process ends, each on its own thread so a
+    /// slow day doesn't hold up the rest.
+    pub fn run(self) {
+        let server = Arc::new(self);
+        for request in server.http.incoming_requests() {
+            let server = Arc::clone(&server);
+            thread::spawn(move || server.respond(request));
+        }
+    }
+
+    fn respond(&self, mut request: Request) {
+        let mut body = Vec::new();
+        let reply = match request.as_reader().read_to_end(&mut body) {
+            Ok(_) => handle(request.method(), request.url(), &body, self.timeout),
+            Err(e) => Reply::error(400, format!("could not read the body: {}", e)),
+        };
+        let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+        let response = Response::from_string(reply.body.to_string())
+            .with_status_code(reply.status)
+            .with_header(header);
+        // The client hanging up early isn't our problem
+        let _ = request.respond(response);
+    }
+}
+
+/// Answers a request for `url` with `body` as the puzzle input.
+pub fn handle(method: &Method, url: &str, body: &[u8], timeout: Option<Duration>) -> Reply {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    let (day, part) = match segments.as_slice() {
+        ["day", day] => (*day, None),
+        ["day", day, "part", part] => (*day, Some(*part)),
+        _ => return Reply::error(404, format!("no route for {}", path)),
+    };
+    let Some(day) = day.parse().ok().filter(|day| solution(*day).is_some()) else {
+        return Reply::error(404, format!("no solution for day `{}`", day));
+    };
+    let part = match part.map(str::parse) {
+        None => Part::Both,
+        Some(Ok(part @ 1..=2)) => Part::new(Some(part)),
+        Some(_) => return Reply::error(404, "parts are 1 and 2"),
+    };
+    if *method != Method::Post {
+        return Reply::error(405, "send the puzzle input with POST");
+    }
+    let Ok(input) = std::str::from_utf8(body) else {
+        return Reply::error(400, "the puzzle input isn't UTF-8");
+    };
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        report::run(day, input, part, None, timeout)
+    }));
+    match result {
+        Ok(Ok(report)) => Reply {
+            status: 200,
+            body: serde_json::to_value(report).expect("reports serialize"),
+        },
+        Ok(Err(Error::Parse(e))) => Reply {
+            status: 422,
+            body: json!({
+                "error": "invalid input",
+                "day": day,
+                "line": e.line,
+                "column": e.column,
+                "expected": e.expected,
+                "found": e.found,
+                "message": e.render(input),
+            }),
+        },
+        Ok(Err(e @ Error::TimedOut(_))) => Reply::error(503, e.to_string()),
+        Ok(Err(e)) => Reply::error(500, e.to_string()),
+        Err(payload) => Reply::error(
+            500,
+            format!("solution panicked: {}", panic_message(&*payload)),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::get_variant;
+
+    use super::*;
+
+    fn post(url: &str, body: &str) -> Reply {
+        handle(&Method::Post, url, body.as_bytes(), None)
+    }
+
+    #[test]
+    fn routes() {
+        let input = get_variant(1, "small").unwrap();
+        let reply = post("/day/1/part/2", &input);
+        assert_eq!(200, reply.status);
+        assert_eq!(31, reply.body["parts"][0]["answer"]);
+        assert_eq!(1, reply.body["parts"].as_array().unwrap().len());
+        assert_eq!(
+            2,
+            post("/day/1", &input).body["parts"]
+                .as_array()
+                .unwrap()
+                .len()
+        );
+
+        assert_eq!(404, post("/day/26/part/1", &input).status);
+        assert_eq!(404, post("/day/1/part/3", &input).status);
+        assert_eq!(404, post("/days/1", &input).status);
+        let get = handle(&Method::Get, "/day/1", input.as_bytes(), None);
+        assert_eq!(405, get.status);
+        assert_eq!(400, handle(&Method::Post, "/day/1", &[0xff], None).status);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let reply = post("/day/1/part/1", "3   4\n4 x\n");
+        assert_eq!(422, reply.status);
+        assert_eq!(1, reply.body["day"]);
+        assert_eq!(2, reply.body["line"]);
+        assert!(reply.body["message"].as_str().unwrap().contains("4 x"));
+    }
+}
@@ -0,0 +1,373 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Serialize, Serializer};
+use tracing::info_span;
+
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+    error::{Error, Result},
+};
+
+/// A day's puzzle: parsed once from the input, then asked for either part's
+/// answer.
+pub trait Solution: Sized {
+    const DAY: usize;
+    /// Has to be bumped whenever a change to the solver could alter its
+    /// answers. Cached answers are keyed by it, so until it is, `run` keeps
+    /// returning the answers from before the change.
+    const VERSION: u32 = 1;
+
+    fn parse(input: &str) -> Result<Self>;
+
+    /// [`Solution::parse`] with puzzle parameters other than the real
+    /// puzzle's, for examples that use smaller ones. Days that don't take any
+    /// refuse them all.
+    fn parse_with(input: &str, params: &Params) -> Result<Self> {
+        read_params(Self::DAY, params, [])?;
+        Self::parse(input)
+    }
+
+    fn part1(&self) -> Answer;
+    fn part2(&self) -> Answer;
+
+    /// Named output that goes with part 2 but isn't the answer itself, like a
+    /// picture of the solution.
+    fn artifacts(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+}
+
+/// Puzzle parameters that aren't in the input, such as the size of a grid,
+/// by name.
+pub type Params = BTreeMap<String, u64>;
+
+/// The parameters a day takes, in the order of `defaults`, with the default
+/// for any missing from `params`. A name the day doesn't take is an error, so
+/// a misspelt one isn't quietly ignored.
+pub fn read_params<const N: usize>(
+    day: usize,
+    params: &Params,
+    defaults: [(&str, u64); N],
+) -> Result<[u64; N]> {
+    if let Some(name) = params
+        .keys()
+        .find(|name| defaults.iter().all(|(known, _)| known != name))
+    {
+        return Err(Error::Param {
+            day,
+            name: name.clone(),
+        });
+    }
+    Ok(defaults.map(|(name, default)| params.get(name).copied().unwrap_or(default)))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+    None,
+}
+
+impl Answer {
+    /// The name of the variant, as reported in JSON output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Int(_) => "int",
+            Self::UInt(_) => "uint",
+            Self::Text(_) => "text",
+            Self::None => "none",
+        }
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Self::Int(v) => serializer.serialize_i64(*v),
+            Self::UInt(v) => serializer.serialize_u64(*v),
+            Self::Text(v) => serializer.serialize_str(v),
+            Self::None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(v) => write!(f, "{}", v),
+            Self::UInt(v) => write!(f, "{}", v),
+            Self::Text(v) => write!(f, "{}", v),
+            Self::None => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident, $inner:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Self::$variant(value as $inner)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Int, i64, i32, i64, isize);
+answer_from!(UInt, u64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn new(part: Option<u8>) -> Self {
+        match part {
+            Some(1) => Self::One,
+            Some(2) => Self::Two,
+            _ => Self::Both,
+        }
+    }
+
+    pub fn one(&self) -> bool {
+        matches!(self, Self::One | Self::Both)
+    }
+
+    pub fn two(&self) -> bool {
+        matches!(self, Self::Two | Self::Both)
+    }
+}
+
+/// Object safe view of a parsed [`Solution`], so days can be stored and run
+/// side by side.
+pub trait Solver {
+    fn part1(&self) -> Answer;
+    fn part2(&self) -> Answer;
+    fn artifacts(&self) -> Vec<(&'static str, String)>;
+}
+
+impl<S: Solution> Solver for S {
+    fn part1(&self) -> Answer {
+        let _span = info_span!("solve", day = S::DAY, part = 1).entered();
+        Solution::part1(self)
+    }
+
+    fn part2(&self) -> Answer {
+        let _span = info_span!("solve", day = S::DAY, part = 2).entered();
+        Solution::part2(self)
+    }
+
+    fn artifacts(&self) -> Vec<(&'static str, String)> {
+        let _span = info_span!("artifacts", day = S::DAY).entered();
+        Solution::artifacts(self)
+    }
+}
+
+/// Parses an input with the given puzzle parameters, or the real puzzle's
+/// when they're empty.
+pub type Parser = fn(&str, &Params) -> Result<Box<dyn Solver>>;
+
+pub(crate) fn parser<S: Solution + 'static>(
+    input: &str,
+    params: &Params,
+) -> Result<Box<dyn Solver>> {
+    let _span = info_span!("parse", day = S::DAY).entered();
+    match S::parse_with(input, params) {
+        Ok(solution) => Ok(Box::new(solution)),
+        Err(e) => Err(e.with_day(S::DAY)),
+    }
+}
+
+/// The event the `dayNN` modules solve. Other events live in `yYYYY`
+/// modules, listed in [`EVENTS`].
+pub const YEAR: u16 = 2024;
+
+pub const DAYS: usize = 25;
+
+const SOLUTIONS: [Parser; DAYS] = [
+    parser::<day01::Lists>,
+    parser::<day02::UnusualData>,
+    parser::<day03::Computer>,
+    parser::<day04::WordSearch>,
+    parser::<day05::Printer>,
+    parser::<day06::Map>,
+    parser::<day07::Bridge>,
+    parser::<day08::Map>,
+    parser::<day09::Computer>,
+    parser::<day10::TopographicMap>,
+    parser::<day11::Pluto>,
+    parser::<day12::Garden>,
+    parser::<day13::Lobby>,
+    parser::<day14::Bathroom>,
+    parser::<day15::Warehouse>,
+    parser::<day16::ReindeerMaze>,
+    parser::<day17::Cpu>,
+    parser::<day18::Computer>,
+    parser::<day19::HotSprings>,
+    parser::<day20::Racetrack>,
+    parser::<day21::Starship>,
+    parser::<day22::MonkeyMarket>,
+    parser::<day23::LanParty>,
+    parser::<day24::Grove>,
+    parser::<day25::Office>,
+];
+
+/// Looks up the parser for a day, numbered from 1.
+pub fn solution(day: usize) -> Option<Parser> {
+    day.checked_sub(1).and_then(|i| SOLUTIONS.get(i)).copied()
+}
+
+pub(crate) const fn version_of<S: Solution>() -> u32 {
+    S::VERSION
+}
+
+const VERSIONS: [u32; DAYS] = [
+    version_of::<day01::Lists>(),
+    version_of::<day02::UnusualData>(),
+    version_of::<day03::Computer>(),
+    version_of::<day04::WordSearch>(),
+    version_of::<day05::Printer>(),
+    version_of::<day06::Map>(),
+    version_of::<day07::Bridge>(),
+    version_of::<day08::Map>(),
+    version_of::<day09::Computer>(),
+    version_of::<day10::TopographicMap>(),
+    version_of::<day11::Pluto>(),
+    version_of::<day12::Garden>(),
+    version_of::<day13::Lobby>(),
+    version_of::<day14::Bathroom>(),
+    version_of::<day15::Warehouse>(),
+    version_of::<day16::ReindeerMaze>(),
+    version_of::<day17::Cpu>(),
+    version_of::<day18::Computer>(),
+    version_of::<day19::HotSprings>(),
+    version_of::<day20::Racetrack>(),
+    version_of::<day21::Starship>(),
+    version_of::<day22::MonkeyMarket>(),
+    version_of::<day23::LanParty>(),
+    version_of::<day24::Grove>(),
+    version_of::<day25::Office>(),
+];
+
+/// The [`Solution::VERSION`] of a day's solver, numbered from 1.
+pub fn version(day: usize) -> Option<u32> {
+    day.checked_sub(1).and_then(|i| VERSIONS.get(i)).copied()
+}
+
+/// Another event's solved days as `(day, parser, version)`, in order.
+pub type Registry = &'static [(usize, Parser, u32)];
+
+/// Events other than [`YEAR`], added to by `new-day`.
+const EVENTS: &[(u16, Registry)] = &[];
+
+fn event(year: u16) -> Option<Registry> {
+    EVENTS
+        .iter()
+        .find(|(y, _)| *y == year)
+        .map(|(_, registry)| *registry)
+}
+
+/// Every event with solutions, oldest first.
+pub fn years() -> Vec<u16> {
+    let mut years = EVENTS.iter().map(|(year, _)| *year).collect::<Vec<_>>();
+    years.push(YEAR);
+    years.sort();
+    years
+}
+
+/// The days solved for `year`.
+pub fn days_in(year: u16) -> Vec<usize> {
+    match year {
+        YEAR => (1..=DAYS).collect(),
+        _ => event(year)
+            .map(|registry| registry.iter().map(|(day, _, _)| *day).collect())
+            .unwrap_or_default(),
+    }
+}
+
+/// [`solution`] for any event.
+pub fn solution_in(year: u16, day: usize) -> Option<Parser> {
+    match year {
+        YEAR => solution(day),
+        _ => event(year)?
+            .iter()
+            .find(|(d, _, _)| *d == day)
+            .map(|(_, parser, _)| *parser),
+    }
+}
+
+/// [`version`] for any event.
+pub fn version_in(year: u16, day: usize) -> Option<u32> {
+    match year {
+        YEAR => version(day),
+        _ => event(year)?
+            .iter()
+            .find(|(d, _, _)| *d == day)
+            .map(|(_, _, version)| *version),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::input::get_variant;
+
+    use super::*;
+
+    #[test]
+    fn registry() {
+        assert!(solution(0).is_none());
+        assert!(solution(26).is_none());
+        let solver =
+            solution(1).unwrap()(&get_variant(1, "small").unwrap(), &Params::new()).unwrap();
+        assert_eq!(Answer::UInt(11), solver.part1());
+        assert_eq!(Answer::UInt(31), solver.part2());
+    }
+
+    #[test]
+    fn params() {
+        let input = get_variant(14, "small").unwrap();
+        let room = Params::from([("width".to_string(), 11), ("height".to_string(), 7)]);
+        let solver = solution(14).unwrap()(&input, &room).unwrap();
+        assert_eq!(Answer::UInt(12), solver.part1());
+
+        let misspelt = Params::from([("widht".to_string(), 11)]);
+        let err = solution(14).unwrap()(&input, &misspelt).err().unwrap();
+        assert_eq!("day 14 has no puzzle parameter `widht`", err.to_string());
+        // Days without parameters take none
+        let input = get_variant(1, "small").unwrap();
+        assert!(solution(1).unwrap()(&input, &room).is_err());
+        let width = Params::from([("width".to_string(), 11)]);
+        assert_eq!(
+            Ok([11, 2]),
+            read_params(14, &width, [("width", 1), ("speed", 2)])
+        );
+    }
+
+    #[test]
+    fn events() {
+        assert!(years().contains(&YEAR));
+        assert_eq!(DAYS, days_in(YEAR).len());
+        assert!(solution_in(YEAR, 25).is_some());
+        assert_eq!(version(6), version_in(YEAR, 6));
+        assert!(days_in(2015).is_empty());
+        assert!(solution_in(2015, 1).is_none());
+    }
+}
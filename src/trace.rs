@@ -0,0 +1,223 @@
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fmt::{self, Write as _},
+    fs::File,
+    io::{self, BufWriter},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span, Subscriber,
+};
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+/// Time spent in spans with the same stack of ancestors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Total {
+    pub time: Duration,
+    pub calls: usize,
+}
+
+/// Totals keyed by the stack of span labels from the root down.
+type Totals = BTreeMap<Vec<String>, Total>;
+
+/// A layer adding up how long is spent in each span, by where it was called
+/// from, for a flame-style summary once everything has finished.
+#[derive(Clone, Default)]
+pub struct Flame {
+    totals: Arc<Mutex<Totals>>,
+}
+
+/// A span's label and how long it's been entered for so far.
+struct Timing {
+    label: String,
+    busy: Duration,
+    entered: Option<Instant>,
+}
+
+/// Writes a span's fields after its name, e.g. `solve day=20 part=2`.
+struct Label<'a>(&'a mut String);
+
+impl Visit for Label<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        let _ = write!(self.0, " {}={:?}", field.name(), value);
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for Flame {
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let span = ctx.span(id).expect("new spans exist");
+        let mut label = span.name().to_string();
+        attrs.record(&mut Label(&mut label));
+        span.extensions_mut().insert(Timing {
+            label,
+            busy: Duration::ZERO,
+            entered: None,
+        });
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(timing) = ctx.span(id).unwrap().extensions_mut().get_mut::<Timing>() {
+            timing.entered = Some(Instant::now());
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(timing) = ctx.span(id).unwrap().extensions_mut().get_mut::<Timing>() {
+            if let Some(entered) = timing.entered.take() {
+                timing.busy += entered.elapsed();
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let span = ctx.span(&id).expect("closed spans exist");
+        let Some(busy) = span.extensions().get::<Timing>().map(|t| t.busy) else {
+            return;
+        };
+        let mut stack = span
+            .scope()
+            .filter_map(|s| s.extensions().get::<Timing>().map(|t| t.label.clone()))
+            .collect::<Vec<_>>();
+        stack.reverse();
+        let mut totals = self.totals.lock().unwrap();
+        let total = totals.entry(stack).or_default();
+        total.time += busy;
+        total.calls += 1;
+    }
+}
+
+impl Flame {
+    pub fn totals(&self) -> Totals {
+        self.totals.lock().unwrap().clone()
+    }
+}
+
+/// Renders `totals` as a tree, each span under the one it was called from
+/// and the slowest first, with its total time, share of its root span's time
+/// and number of calls.
+pub fn summary(totals: &Totals) -> String {
+    let width = totals
+        .keys()
+        .map(|stack| 2 * (stack.len() - 1) + stack.last().unwrap().len())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::new();
+    summarise(totals, &[], None, width, &mut out);
+    out
+}
+
+/// Writes the spans called from `parent`, and theirs in turn.
+fn summarise(
+    totals: &Totals,
+    parent: &[String],
+    root: Option<Duration>,
+    width: usize,
+    out: &mut String,
+) {
+    let mut children = totals
+        .iter()
+        .filter(|(stack, _)| stack.len() == parent.len() + 1 && stack.starts_with(parent))
+        .collect::<Vec<_>>();
+    children.sort_by_key(|(_, total)| Reverse(total.time));
+    for (stack, total) in children {
+        let root = root.unwrap_or(total.time);
+        let share = if root.is_zero() {
+            100.0
+        } else {
+            100.0 * total.time.as_secs_f64() / root.as_secs_f64()
+        };
+        let name = format!("{}{}", "  ".repeat(parent.len()), stack.last().unwrap());
+        let _ = writeln!(
+            out,
+            "{:width$}  {:>10.1?}  {:>5.1}%  {:>6} {}",
+            name,
+            total.time,
+            share,
+            total.calls,
+            if total.calls == 1 { "call" } else { "calls" },
+            width = width
+        );
+        summarise(totals, stack, Some(root), width, out);
+    }
+}
+
+/// Tracing set up for a run; report it with [`Tracing::finish`].
+pub struct Tracing {
+    flame: Option<Flame>,
+    chrome: Option<FlushGuard>,
+}
+
+impl Tracing {
+    /// Starts collecting spans for a flame summary and/or a Chrome trace
+    /// written to `chrome`, if either is wanted.
+    pub fn start(flame: bool, chrome: Option<&Path>) -> io::Result<Self> {
+        let flame = flame.then(Flame::default);
+        let (chrome_layer, chrome) = match chrome {
+            Some(path) => {
+                let file = File::create(path)
+                    .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+                let file = BufWriter::new(file);
+                let (layer, guard) = ChromeLayerBuilder::new()
+                    .writer(file)
+                    .include_args(true)
+                    .build();
+                (Some(layer), Some(guard))
+            }
+            None => (None, None),
+        };
+        if flame.is_some() || chrome.is_some() {
+            let subscriber = tracing_subscriber::registry()
+                .with(flame.clone())
+                .with(chrome_layer);
+            tracing::subscriber::set_global_default(subscriber).map_err(io::Error::other)?;
+        }
+        Ok(Self { flame, chrome })
+    }
+
+    /// Writes out the Chrome trace and returns the flame summary, if they
+    /// were asked for.
+    pub fn finish(self) -> Option<String> {
+        drop(self.chrome);
+        self.flame.map(|flame| summary(&flame.totals()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tracing::info_span;
+
+    use super::*;
+
+    #[test]
+    fn flame() {
+        let flame = Flame::default();
+        let subscriber = tracing_subscriber::registry().with(flame.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            let _solve = info_span!("solve", day = 20).entered();
+            for _ in 0..3 {
+                let _bfs = info_span!("bfs").entered();
+            }
+        });
+        let totals = flame.totals();
+        let stacks = totals.keys().cloned().collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                vec!["solve day=20".to_string()],
+                vec!["solve day=20".to_string(), "bfs".to_string()],
+            ],
+            stacks
+        );
+        assert_eq!(3, totals[&stacks[1]].calls);
+        let summary = summary(&totals);
+        assert!(summary.starts_with("solve day=20"));
+        assert!(summary.lines().nth(1).unwrap().starts_with("  bfs"));
+        assert!(summary.contains("100.0%"));
+        assert!(summary.contains("3 calls"));
+    }
+}
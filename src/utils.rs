@@ -1,16 +1,216 @@
-#![allow(dead_code)]
-use std::fs::read_to_string;
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
 
-pub fn get_input(day: usize) -> String {
-    read_to_string(format!("inputs/day{}.txt", day)).unwrap()
+use crate::{geometry::Point, parse::ParseError};
+
+/// A dense 2D map stored row by row in a flat `Vec`, indexed by [`Point`].
+/// Lookups outside the map return `None` instead of panicking, so callers
+/// don't need their own bounds checks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses a rectangular character map, converting each character with
+    /// `f`. Characters it returns `None` for are reported as not being
+    /// `expected`.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in input.lines() {
+            let mut count = 0;
+            for (i, c) in line.char_indices() {
+                let cell = f(c).ok_or_else(|| {
+                    ParseError::new(line, &line[i..i + c.len_utf8()], expected).within(input, line)
+                })?;
+                cells.push(cell);
+                count += 1;
+            }
+            match width {
+                None => width = Some(count),
+                Some(width) if width != count => {
+                    return Err(ParseError::new(
+                        input,
+                        line,
+                        format!("row of {} cells", width),
+                    ))
+                }
+                Some(_) => (),
+            }
+            height += 1;
+        }
+        let width = width
+            .filter(|width| *width > 0)
+            .ok_or_else(|| ParseError::eof(input, "map"))?;
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        (0..self.height as isize).contains(&p.y) && (0..self.width as isize).contains(&p.x)
+    }
+
+    fn index_of(&self, p: Point) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.index_of(p).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.index_of(p).map(|i| &mut self.cells[i])
+    }
+
+    /// Every point in the map, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height * width)
+            .map(move |i| Point::new((i / width) as isize, (i % width) as isize))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The orthogonal neighbours of `p` that are inside the map.
+    pub fn neighbours(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbours().filter(|p| self.contains(*p))
+    }
+
+    /// All eight neighbours of `p`, diagonals included, that are inside the
+    /// map.
+    pub fn surrounding(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.surrounding().filter(|p| self.contains(*p))
+    }
+
+    /// The first point, row by row, holding `value`.
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.iter().find(|(_, v)| *v == value).map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
 }
 
-pub fn get_small(day: usize) -> String {
-    read_to_string(format!("inputs/day{}_small.txt", day)).unwrap()
+impl Grid<char> {
+    /// Parses a rectangular character map as is.
+    pub fn from_chars(input: &str) -> Result<Self, ParseError> {
+        Self::parse(input, "character", Some)
+    }
 }
 
-pub fn get_smalln(day: usize, n: usize) -> String {
-    read_to_string(format!("inputs/day{}_small{}.txt", day, n)).unwrap()
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", p))
+    }
 }
 
-pub type Point = (isize, isize);
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", p))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Rows without cells can't be chunked, but are still lines
+        if self.width == 0 {
+            return (0..self.height).try_for_each(|_| writeln!(f));
+        }
+        for row in self.cells.chunks(self.width) {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let grid = Grid::parse("#.\n.#\n.S\n", "`#`, `.` or `S`", |c| {
+            "#.S".contains(c).then_some(c)
+        })
+        .unwrap();
+        assert_eq!((2, 3), (grid.width(), grid.height()));
+        assert_eq!(Some(Point::new(2, 1)), grid.find(&'S'));
+        assert_eq!(Some(&'#'), grid.get(Point::new(1, 1)));
+        assert_eq!(None, grid.get(Point::new(1, 2)));
+        assert_eq!(None, grid.get(Point::new(-1, 0)));
+        assert_eq!("#.\n.#\n.S\n", grid.to_string());
+
+        let err =
+            Grid::parse("#.\n.x\n", "`#` or `.`", |c| "#.".contains(c).then_some(c)).unwrap_err();
+        assert_eq!((2, 2), (err.line, err.column));
+        let err = Grid::from_chars("#.\n.\n").unwrap_err();
+        assert_eq!("row of 2 cells", err.expected);
+    }
+
+    #[test]
+    fn empty_rows() {
+        assert_eq!("\n\n", Grid::new(0, 2, '.').to_string());
+        assert_eq!("", Grid::new(3, 0, '.').to_string());
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 2, 0);
+        assert_eq!(
+            vec![Point::new(0, 1), Point::new(1, 0)],
+            grid.neighbours(Point::ORIGIN).collect::<Vec<_>>()
+        );
+        assert_eq!(5, grid.surrounding(Point::new(1, 1)).count());
+        assert_eq!(6, grid.points().count());
+    }
+}
@@ -0,0 +1,330 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{Error, Result},
+    input::{self, Source},
+    solution::{solution_in, Answer, Params},
+};
+
+/// Expected answers for the full inputs, keyed by day and part.
+///
+/// Stored as one `day part answer` line per entry, where the answer is written
+/// the way it prints. Blank lines and lines starting with `#` are skipped.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(usize, u8), String>);
+
+impl Answers {
+    /// `answers.txt` next to the inputs, so it follows `AOC_INPUT_DIR`.
+    pub fn default_path() -> PathBuf {
+        input::dir().join("answers.txt")
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::parse(&read(path)?).map_err(|reason| Error::Input {
+            source: path.display().to_string(),
+            reason,
+        })
+    }
+
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut answers = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(3, ' ');
+            let entry = match (fields.next(), fields.next(), fields.next()) {
+                (Some(day), Some(part), Some(answer)) => day
+                    .parse()
+                    .ok()
+                    .zip(part.parse().ok().filter(|part| (1..=2).contains(part)))
+                    .map(|key| (key, answer.trim().to_string())),
+                _ => None,
+            };
+            let (key, answer) = entry.ok_or_else(|| {
+                format!(
+                    "line {}: expected `day part answer`, found `{}`",
+                    i + 1,
+                    line
+                )
+            })?;
+            answers.insert(key, answer);
+        }
+        Ok(Self(answers))
+    }
+
+    pub fn get(&self, day: usize, part: u8) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    /// The parts of `day` with an answer to check. Parts recorded as having no
+    /// answer, like day 25's second, are left out.
+    pub fn parts(&self, day: usize) -> Vec<u8> {
+        let none = Answer::None.to_string();
+        [1, 2]
+            .into_iter()
+            .filter(|part| self.get(day, *part).is_some_and(|answer| answer != none))
+            .collect()
+    }
+
+    /// Checks a computed answer against the expected one.
+    pub fn check(&self, day: usize, part: u8, answer: &Answer) -> Outcome {
+        Outcome::new(self.get(day, part), answer)
+    }
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|e| Error::Input {
+        source: path.display().to_string(),
+        reason: e.to_string(),
+    })
+}
+
+/// An example input, `dayNN_VARIANT.txt`, with a sidecar of the answers it
+/// should give, `dayNN_VARIANT.answers`.
+///
+/// The sidecar holds one `part answer` line per part to check, with the same
+/// blank and `#` lines allowed as in [`Answers`]. Parts left out aren't run.
+/// Examples that use puzzle parameters other than the real puzzle's, like a
+/// smaller grid, set them with `name value` lines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixture {
+    pub year: u16,
+    pub day: usize,
+    pub variant: String,
+    pub params: Params,
+    pub answers: BTreeMap<u8, String>,
+}
+
+impl Fixture {
+    /// Every `dayNN_small*.txt` in `dir`, which holds `year`'s inputs, with
+    /// its sidecar. A fixture without a sidecar is an error, so none are
+    /// skipped by accident.
+    pub fn discover(dir: &Path, year: u16) -> Result<Vec<Self>> {
+        let entries = fs::read_dir(dir).map_err(|e| Error::Input {
+            source: dir.display().to_string(),
+            reason: e.to_string(),
+        })?;
+        let mut fixtures = Vec::new();
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let Some((day, variant)) = name
+                .strip_prefix("day")
+                .and_then(|name| name.strip_suffix(".txt"))
+                .and_then(|name| name.split_once('_'))
+                .filter(|(_, variant)| variant.starts_with("small"))
+                .and_then(|(day, variant)| Some((day.parse().ok()?, variant)))
+            else {
+                continue;
+            };
+            let (params, answers) = Self::sidecar(&sidecar_path(dir, day, variant))?;
+            fixtures.push(Self {
+                year,
+                day,
+                variant: variant.to_string(),
+                params,
+                answers,
+            });
+        }
+        fixtures.sort_by(|a, b| (a.day, &a.variant).cmp(&(b.day, &b.variant)));
+        Ok(fixtures)
+    }
+
+    /// The puzzle parameters the sidecar of `day`'s `variant` sets, or none if
+    /// it has no sidecar.
+    pub fn params(year: u16, day: usize, variant: &str) -> Result<Params> {
+        let path = sidecar_path(&input::year_dir(year), day, variant);
+        if !path.exists() {
+            return Ok(Params::new());
+        }
+        Ok(Self::sidecar(&path)?.0)
+    }
+
+    fn sidecar(path: &Path) -> Result<(Params, BTreeMap<u8, String>)> {
+        Self::parse(&read(path)?).map_err(|reason| Error::Input {
+            source: path.display().to_string(),
+            reason,
+        })
+    }
+
+    fn parse(text: &str) -> Result<(Params, BTreeMap<u8, String>), String> {
+        let mut params = Params::new();
+        let mut answers = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || {
+                format!(
+                    "line {}: expected `part answer` or `name value`, found `{}`",
+                    i + 1,
+                    line
+                )
+            };
+            let (first, rest) = line.split_once(' ').ok_or_else(invalid)?;
+            let rest = rest.trim();
+            if first.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                let value = rest.parse().map_err(|_| invalid())?;
+                params.insert(first.to_string(), value);
+            } else {
+                let part = first
+                    .parse()
+                    .ok()
+                    .filter(|part| (1..=2).contains(part))
+                    .ok_or_else(invalid)?;
+                answers.insert(part, rest.to_string());
+            }
+        }
+        Ok((params, answers))
+    }
+
+    /// Solves the parts in the sidecar with its puzzle parameters, reading the
+    /// fixture from the inputs directory.
+    pub fn check(&self) -> Result<Vec<(u8, Outcome)>> {
+        if self.answers.is_empty() {
+            return Ok(Vec::new());
+        }
+        let input = Source::in_year(self.year, self.day, Some(&self.variant)).read()?;
+        let parse = solution_in(self.year, self.day).expect("fixtures are named after days");
+        let solver = parse(&input, &self.params)?;
+        Ok(self
+            .answers
+            .iter()
+            .map(|(part, expected)| {
+                let answer = match part {
+                    1 => solver.part1(),
+                    _ => solver.part2(),
+                };
+                (*part, Outcome::new(Some(expected), &answer))
+            })
+            .collect())
+    }
+}
+
+fn sidecar_path(dir: &Path, day: usize, variant: &str) -> PathBuf {
+    dir.join(format!("day{:02}_{}.answers", day, variant))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// No expected answer is recorded for this part.
+    Missing,
+}
+
+impl Outcome {
+    fn new(expected: Option<&str>, answer: &Answer) -> Self {
+        match expected {
+            None => Self::Missing,
+            Some(expected) if expected == answer.to_string() => Self::Pass,
+            Some(expected) => Self::Fail {
+                expected: expected.to_string(),
+                actual: answer.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail { expected, actual } => {
+                write!(f, "FAIL: expected {}, got {}", expected, actual)
+            }
+            Self::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// Running totals of outcomes across a verify run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+}
+
+impl Summary {
+    pub fn add(&mut self, outcome: &Outcome) {
+        match outcome {
+            Outcome::Pass => self.passed += 1,
+            Outcome::Fail { .. } => self.failed += 1,
+            Outcome::Missing => self.missing += 1,
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} missing",
+            self.passed, self.failed, self.missing
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let answers = Answers::parse("# comment\n1 1 11\n\n23 2 co,de,ka,ta\n").unwrap();
+        assert_eq!(Some("11"), answers.get(1, 1));
+        assert_eq!(Some("co,de,ka,ta"), answers.get(23, 2));
+        assert_eq!(None, answers.get(1, 2));
+        assert!(Answers::parse("1 3 11").is_err());
+        assert!(Answers::parse("1 1").is_err());
+    }
+
+    #[test]
+    fn sidecar() {
+        let (params, answers) = Fixture::parse("# only part 1\n1 4,6,3\n").unwrap();
+        assert!(params.is_empty());
+        assert_eq!(BTreeMap::from([(1, "4,6,3".to_string())]), answers);
+        assert_eq!(Ok(Default::default()), Fixture::parse(""));
+        assert!(Fixture::parse("3 11").is_err());
+        assert!(Fixture::parse("1").is_err());
+
+        let (params, answers) = Fixture::parse("width 7\nbytes 12\n1 22\n").unwrap();
+        assert_eq!(Some(&7), params.get("width"));
+        assert_eq!(Some(&12), params.get("bytes"));
+        assert_eq!(1, answers.len());
+        assert!(Fixture::parse("width seven").is_err());
+    }
+
+    #[test]
+    fn check() {
+        let answers = Answers::parse("1 1 11\n1 2 31").unwrap();
+        assert_eq!(Outcome::Pass, answers.check(1, 1, &Answer::UInt(11)));
+        assert_eq!(
+            Outcome::Fail {
+                expected: "31".to_string(),
+                actual: "30".to_string()
+            },
+            answers.check(1, 2, &Answer::UInt(30))
+        );
+        assert_eq!(Outcome::Missing, answers.check(2, 1, &Answer::UInt(2)));
+    }
+
+    #[test]
+    fn parts() {
+        let answers = Answers::parse("1 1 11\n1 2 31\n24 1 2024\n25 1 3\n25 2 -\n").unwrap();
+        assert_eq!(vec![1, 2], answers.parts(1));
+        assert_eq!(vec![1], answers.parts(24));
+        assert_eq!(vec![1], answers.parts(25));
+        assert!(answers.parts(2).is_empty());
+    }
+}
@@ -0,0 +1,28 @@
+use aoc2024::{
+    day16::ReindeerMaze,
+    day17::Cpu,
+    day24::Grove,
+    input::get_variant,
+    solution::{solution, Answer, Params, Solution},
+};
+
+#[test]
+fn day_types() {
+    let maze = ReindeerMaze::new(&get_variant(16, "small").unwrap()).unwrap();
+    assert_eq!(7036, maze.lowest_score());
+    assert_eq!(45, maze.best_path_tiles());
+
+    let mut cpu = Cpu::new(&get_variant(17, "small").unwrap()).unwrap();
+    cpu.run();
+    assert_eq!("4,6,3,5,6,3,5,2,1,0", cpu.output());
+
+    let grove = Grove::parse(&get_variant(24, "small").unwrap()).unwrap();
+    assert_eq!(Answer::UInt(2024), grove.part1());
+}
+
+#[test]
+fn registry() {
+    let parse = solution(1).unwrap();
+    let solver = parse(&get_variant(1, "small").unwrap(), &Params::new()).unwrap();
+    assert_eq!(Answer::UInt(11), solver.part1());
+}
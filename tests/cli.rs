@@ -0,0 +1,44 @@
+use std::{env, fs, process::Command};
+
+use serde_json::Value;
+
+/// `run all` reports a day with an input that once aborted the process, and
+/// still reports every other day.
+#[test]
+fn run_all_with_bad_input() {
+    let dir = env::temp_dir().join(format!("aoc2024-cli-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for entry in fs::read_dir("inputs").unwrap().flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.ends_with("_small.txt") {
+            fs::copy(entry.path(), dir.join(name)).unwrap();
+        }
+    }
+    fs::write(dir.join("day19_small.txt"), "r, \n\nrx\n").unwrap();
+    fs::write(
+        dir.join("day24_small.txt"),
+        "x00: 1\n\nq00 AND x00 -> z00\nz00 OR x00 -> q00\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc2024"))
+        .args(["run", "all", "-v", "small", "--no-cache", "-f", "json"])
+        .env("AOC_INPUT_DIR", &dir)
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(!output.status.success());
+    let reports = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(25, reports.len());
+    for day in [19, 24] {
+        let error = reports[day - 1]["error"].as_str().unwrap();
+        assert!(error.starts_with("invalid input"), "day {}: {}", day, error);
+    }
+    assert_eq!(11, reports[0]["parts"][0]["answer"]);
+    assert_eq!("co,de,ka,ta", reports[22]["parts"][1]["answer"]);
+}
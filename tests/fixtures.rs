@@ -0,0 +1,30 @@
+use aoc2024::{
+    input,
+    solution::years,
+    verify::{Fixture, Outcome},
+};
+
+/// Checks every event's example inputs against their `.answers` sidecars,
+/// reporting all failures at once.
+#[test]
+fn fixtures() {
+    let mut failures = Vec::new();
+    for year in years() {
+        let dir = input::year_dir(year);
+        let fixtures = Fixture::discover(&dir, year).unwrap_or_else(|e| panic!("{}", e));
+        assert!(!fixtures.is_empty(), "no fixtures in {:?}", dir);
+        for fixture in &fixtures {
+            let name = format!("{} day {} {}", year, fixture.day, fixture.variant);
+            match fixture.check() {
+                Ok(outcomes) => failures.extend(
+                    outcomes
+                        .into_iter()
+                        .filter(|(_, outcome)| *outcome != Outcome::Pass)
+                        .map(|(part, outcome)| format!("{} part {}: {}", name, part, outcome)),
+                ),
+                Err(e) => failures.push(format!("{}: {}", name, e)),
+            }
+        }
+    }
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
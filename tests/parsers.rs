@@ -0,0 +1,118 @@
+use std::{sync::mpsc, thread, time::Duration};
+
+use aoc2024::{
+    generate::generate,
+    solution::{solution, Params, DAYS},
+};
+use proptest::prelude::*;
+
+/// Parses `input` for every day on another thread, failing if a parser
+/// panics or takes too long. Errors are fine; most of these inputs are
+/// nonsense.
+fn parses_cleanly(input: &str) -> Result<(), TestCaseError> {
+    for day in 1..=DAYS {
+        let parse = solution(day).unwrap();
+        let (tx, rx) = mpsc::channel();
+        let owned = input.to_string();
+        thread::spawn(move || tx.send(parse(&owned, &Params::new()).is_ok()).unwrap());
+        match rx.recv_timeout(Duration::from_secs(5)) {
+            Ok(_) => {}
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                return Err(TestCaseError::fail(format!("day {} parser hung", day)))
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(TestCaseError::fail(format!("day {} parser panicked", day)))
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
+enum Mutation {
+    Delete(usize),
+    Insert(usize, char),
+    Replace(usize, char),
+    Truncate(usize),
+    DuplicateLine(usize),
+    SwapLines(usize, usize),
+}
+
+impl Mutation {
+    fn apply(&self, input: &str) -> String {
+        let mut chars = input.chars().collect::<Vec<_>>();
+        let at = |i: usize, len: usize| if len == 0 { 0 } else { i % len };
+        match *self {
+            Self::Delete(i) if !chars.is_empty() => {
+                chars.remove(at(i, chars.len()));
+            }
+            Self::Insert(i, c) => chars.insert(at(i, chars.len() + 1), c),
+            Self::Replace(i, c) if !chars.is_empty() => {
+                let i = at(i, chars.len());
+                chars[i] = c;
+            }
+            Self::Truncate(i) => chars.truncate(at(i, chars.len() + 1)),
+            Self::DuplicateLine(i) | Self::SwapLines(i, _) => {
+                let mut lines = input.lines().map(String::from).collect::<Vec<_>>();
+                if lines.is_empty() {
+                    return input.to_string();
+                }
+                let i = at(i, lines.len());
+                match *self {
+                    Self::SwapLines(_, j) => {
+                        let j = at(j, lines.len());
+                        lines.swap(i, j);
+                    }
+                    _ => lines.insert(i, lines[i].clone()),
+                }
+                return lines.join("\n") + "\n";
+            }
+            _ => {}
+        }
+        chars.into_iter().collect()
+    }
+}
+
+/// Characters that mean something to at least one day's format.
+const ALPHABET: &str = "0123456789-+,:|=.#^<>v@OSEAXMpxyz \n";
+
+fn puzzle_char() -> impl Strategy<Value = char> {
+    prop::sample::select(ALPHABET.chars().collect::<Vec<_>>())
+}
+
+fn mutation() -> impl Strategy<Value = Mutation> {
+    prop_oneof![
+        any::<usize>().prop_map(Mutation::Delete),
+        (any::<usize>(), puzzle_char()).prop_map(|(i, c)| Mutation::Insert(i, c)),
+        (any::<usize>(), any::<char>()).prop_map(|(i, c)| Mutation::Replace(i, c)),
+        any::<usize>().prop_map(Mutation::Truncate),
+        any::<usize>().prop_map(Mutation::DuplicateLine),
+        (any::<usize>(), any::<usize>()).prop_map(|(i, j)| Mutation::SwapLines(i, j)),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_bytes(bytes in prop::collection::vec(any::<u8>(), 0..256)) {
+        parses_cleanly(&String::from_utf8_lossy(&bytes))?;
+    }
+
+    #[test]
+    fn arbitrary_text(chars in prop::collection::vec(puzzle_char(), 0..256)) {
+        parses_cleanly(&chars.into_iter().collect::<String>())?;
+    }
+
+    #[test]
+    fn mutated_inputs(
+        day in 1..=DAYS,
+        size in 1..20_usize,
+        seed in any::<u64>(),
+        mutations in prop::collection::vec(mutation(), 1..6),
+    ) {
+        let mut input = generate(day, size, seed).unwrap();
+        for mutation in &mutations {
+            input = mutation.apply(&input);
+        }
+        parses_cleanly(&input)?;
+    }
+}
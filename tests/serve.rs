@@ -0,0 +1,62 @@
+use std::thread;
+
+use aoc2024::{input::get_variant, serve::Server};
+use serde_json::Value;
+
+/// Starts a server on a free port and returns its base URL.
+fn start() -> String {
+    let server = Server::bind("127.0.0.1:0", None).unwrap();
+    let url = format!("http://{}", server.addr());
+    thread::spawn(move || server.run());
+    url
+}
+
+fn post(url: &str, body: &str) -> (u16, Value) {
+    let response = match ureq::post(url).send_string(body) {
+        Ok(response) => response,
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => panic!("{}", e),
+    };
+    let status = response.status();
+    let body = response.into_string().unwrap();
+    (status, serde_json::from_str(&body).unwrap())
+}
+
+#[test]
+fn loopback() {
+    let url = start();
+    let (status, body) = post(
+        &format!("{}/day/7/part/1", url),
+        &get_variant(7, "small").unwrap(),
+    );
+    assert_eq!(200, status);
+    assert_eq!(7, body["day"]);
+    assert_eq!(3749, body["parts"][0]["answer"]);
+    assert!(body["parts"][0]["time_ns"].is_u64());
+
+    let (status, body) = post(&format!("{}/day/7", url), "190: 10 x\n");
+    assert_eq!(422, status);
+    assert_eq!(1, body["line"]);
+
+    let (status, _) = post(&format!("{}/day/0/part/1", url), "");
+    assert_eq!(404, status);
+}
+
+/// Inputs that used to take the whole server down are turned away, and the
+/// server carries on answering.
+#[test]
+fn survives_bad_input() {
+    let url = start();
+    let (status, _) = post(&format!("{}/day/19/part/1", url), "r, \n\nrx\n");
+    assert_eq!(422, status);
+    let cycle = "x00: 1\n\nq00 AND x00 -> z00\nz00 OR x00 -> q00\n";
+    let (status, _) = post(&format!("{}/day/24/part/1", url), cycle);
+    assert_eq!(422, status);
+
+    let (status, body) = post(
+        &format!("{}/day/19/part/1", url),
+        &get_variant(19, "small").unwrap(),
+    );
+    assert_eq!(200, status);
+    assert_eq!(6, body["parts"][0]["answer"]);
+}